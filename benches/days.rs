@@ -0,0 +1,67 @@
+//! Times every day's generator and parts separately.
+//!
+//! Real inputs are used when present (see `inputs::input_dir`), otherwise,
+//! with the `synth` feature, a synthesized input of typical size. Filter with
+//! the usual criterion arguments, e.g. `cargo bench --bench days -- day08/part1`.
+
+use std::hint::black_box;
+
+#[cfg(feature = "synth")]
+use advent_of_code_2025::synth;
+use advent_of_code_2025::{
+    SOLUTIONS, inputs,
+    solution::{Answer, Part},
+};
+use criterion::{Criterion, criterion_group, criterion_main};
+
+#[cfg(feature = "synth")]
+const SEED: u64 = 2025;
+
+fn input(day: u8) -> Option<(String, &'static str)> {
+    let path = inputs::input_path(&inputs::input_dir(), day);
+    if let Ok(input) = inputs::read_input(&path) {
+        return Some((input, "real"));
+    }
+
+    #[cfg(feature = "synth")]
+    if let Some(synth) = synth::synth(day) {
+        return Some((synth.input(SEED, synth.typical_size), "synthesized"));
+    }
+
+    None
+}
+
+fn days(c: &mut Criterion) {
+    for solution in SOLUTIONS {
+        let day = solution.day();
+        let Some((input, kind)) = input(day) else {
+            eprintln!("skipping day{day}: no input");
+            continue;
+        };
+        let parsed = match solution.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                eprintln!("skipping day{day}: {e}");
+                continue;
+            }
+        };
+
+        eprintln!("day{day}: {kind} input");
+        let mut group = c.benchmark_group(format!("day{day:02}"));
+        group.bench_function("generator", |b| {
+            b.iter(|| solution.parse(black_box(&input)))
+        });
+        for part in Part::ALL {
+            if solution.solve(&parsed, part) == Answer::Unsolved {
+                continue;
+            }
+            group.bench_function(format!("part{part}"), |b| {
+                b.iter(|| solution.solve(black_box(&parsed), part))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
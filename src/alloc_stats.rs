@@ -0,0 +1,184 @@
+//! Allocation accounting: how many allocations a piece of code makes, how
+//! many bytes it asks for, and how much it has live at its peak.
+//!
+//! Only [`CountingAlloc`] counts, so it must be the global allocator, which the
+//! runner makes it with the `alloc-stats` feature. Counters are per thread, so
+//! jobs running in parallel don't see each other's allocations.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+};
+
+use serde::Serialize;
+
+/// The system allocator, counting as it goes.
+pub struct CountingAlloc;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct AllocStats {
+    /// Allocations and reallocations.
+    pub allocations: u64,
+    /// Bytes asked for, counting only the growth of a reallocation.
+    pub bytes: u64,
+    /// The most bytes live at once, above what was live at the start.
+    pub peak_bytes: u64,
+}
+
+struct Counters {
+    allocations: Cell<u64>,
+    bytes: Cell<u64>,
+    /// Can go negative when freeing what was allocated before, or elsewhere.
+    live: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+thread_local! {
+    static COUNTERS: Counters = const {
+        Counters {
+            allocations: Cell::new(0),
+            bytes: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+/// Counts a change of `delta` live bytes, `requested` of them newly asked for.
+fn record(requested: usize, delta: i64) {
+    // Fails only while the thread is being torn down, which isn't worth counting
+    let _ = COUNTERS.try_with(|c| {
+        c.allocations.set(c.allocations.get() + 1);
+        c.bytes.set(c.bytes.get() + requested as u64);
+        c.live.set(c.live.get() + delta);
+        c.peak.set(c.peak.get().max(c.live.get()));
+    });
+}
+
+fn release(size: usize) {
+    let _ = COUNTERS.try_with(|c| c.live.set(c.live.get() - size as i64));
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record(layout.size(), layout.size() as i64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        release(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            let delta = new_size as i64 - layout.size() as i64;
+            record(new_size.saturating_sub(layout.size()), delta);
+        }
+        new_ptr
+    }
+}
+
+/// Calls `f`, returning its result and the allocations it made on this
+/// thread. All zeros unless [`CountingAlloc`] is the global allocator.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let (allocations, bytes, live, outer_peak) = COUNTERS.with(|c| {
+        let start = (
+            c.allocations.get(),
+            c.bytes.get(),
+            c.live.get(),
+            c.peak.get(),
+        );
+        c.peak.set(c.live.get());
+        start
+    });
+    let res = f();
+    let stats = COUNTERS.with(|c| {
+        let peak = c.peak.get();
+        // Keep the peak of any measurement this one is nested in
+        c.peak.set(peak.max(outer_peak));
+        AllocStats {
+            allocations: c.allocations.get() - allocations,
+            bytes: c.bytes.get() - bytes,
+            peak_bytes: (peak - live).max(0) as u64,
+        }
+    });
+
+    (res, stats)
+}
+
+/// Sizes in bytes, in the largest binary unit that keeps them above 1.
+fn fmt_bytes(f: &mut fmt::Formatter<'_>, bytes: u64) -> fmt::Result {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return write!(f, "{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    write!(f, "{size:.1} {}", UNITS[unit])
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.allocations == 1 { "" } else { "s" };
+        write!(f, "{} alloc{plural}, ", self.allocations)?;
+        fmt_bytes(f, self.bytes)?;
+        f.write_str(", peak ")?;
+        fmt_bytes(f, self.peak_bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn measure_test() {
+        // The test harness doesn't install the allocator, so call it directly
+        let alloc = |size| unsafe {
+            let layout = Layout::from_size_align(size, 8).unwrap();
+            (CountingAlloc.alloc(layout), layout)
+        };
+        let free = |(ptr, layout)| unsafe { CountingAlloc.dealloc(ptr, layout) };
+
+        let (_, stats) = measure(|| {
+            let a = alloc(1000);
+            let b = alloc(3000);
+            free(a);
+            let (_, inner) = measure(|| free(alloc(500)));
+            assert_eq!(inner.peak_bytes, 500);
+
+            let grown = unsafe { CountingAlloc.realloc(b.0, b.1, 5000) };
+            free((grown, Layout::from_size_align(5000, 8).unwrap()));
+        });
+
+        assert_eq!(
+            stats,
+            AllocStats {
+                allocations: 4,
+                bytes: 6500,
+                peak_bytes: 5000,
+            }
+        );
+        assert_eq!(stats.to_string(), "4 allocs, 6.3 KiB, peak 4.9 KiB");
+        assert_eq!(measure(|| ()).1.to_string(), "0 allocs, 0 B, peak 0 B");
+    }
+}
@@ -0,0 +1,182 @@
+//! The answers manifest: known answers keyed by input profile and day.
+//!
+//! ```toml
+//! [default]
+//! day1 = [1029, 5892]
+//! day12 = [569]
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+};
+
+use crate::{inputs, solution::Part};
+
+/// Default manifest, relative to the crate root.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// The manifest, overridden by the `AOC_ANSWERS` environment variable.
+pub fn answers_path() -> PathBuf {
+    env::var_os("AOC_ANSWERS").map_or_else(|| PathBuf::from(ANSWERS_FILE), PathBuf::from)
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    profiles: BTreeMap<String, BTreeMap<u8, Vec<String>>>,
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let table: toml::Table = s.parse().map_err(|e| format!("{e}"))?;
+        let mut answers = Answers::default();
+
+        for (profile, days) in table {
+            let days = days
+                .as_table()
+                .ok_or_else(|| format!("[{profile}] must be a table of days"))?;
+
+            for (key, parts) in days {
+                let day = key
+                    .strip_prefix("day")
+                    .and_then(|day| day.parse().ok())
+                    .ok_or_else(|| format!("[{profile}] {key}: expected a key like day1"))?;
+                let parts = parts
+                    .as_array()
+                    .filter(|parts| parts.len() <= Part::ALL.len())
+                    .ok_or_else(|| format!("[{profile}] {key}: expected up to 2 answers"))?
+                    .iter()
+                    .map(|answer| match answer {
+                        toml::Value::Integer(n) => Ok(n.to_string()),
+                        toml::Value::String(s) => Ok(s.clone()),
+                        _ => Err(format!("[{profile}] {key}: {answer} is not an answer")),
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                answers.insert(&profile, day, parts);
+            }
+        }
+
+        Ok(answers)
+    }
+
+    /// Loads a manifest; a missing file is an empty manifest.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    /// Loads the `answers.toml` from `input_dir`, merged on top of
+    /// [`answers_path`] if `input_dir` is [`inputs::input_dir`] or
+    /// `AOC_ANSWERS` is set. The repo's manifest only knows the repo's inputs,
+    /// so any other directory brings its own answers or none.
+    pub fn load_all(input_dir: &Path) -> Result<Self, String> {
+        let mut answers = if is_same_dir(input_dir, &inputs::input_dir())
+            || env::var_os("AOC_ANSWERS").is_some()
+        {
+            Self::load(&answers_path())?
+        } else {
+            Self::default()
+        };
+        answers.merge(Self::load(&input_dir.join(ANSWERS_FILE))?);
+
+        Ok(answers)
+    }
+
+    pub fn insert(&mut self, profile: &str, day: u8, parts: Vec<String>) {
+        self.profiles
+            .entry(profile.to_string())
+            .or_default()
+            .insert(day, parts);
+    }
+
+    /// Adds every answer in `other`, replacing any already known for the same day.
+    pub fn merge(&mut self, other: Self) {
+        for (profile, days) in other.profiles {
+            self.profiles.entry(profile).or_default().extend(days);
+        }
+    }
+
+    pub fn get(&self, profile: &str, day: u8, part: Part) -> Option<&str> {
+        self.profiles
+            .get(profile)?
+            .get(&day)?
+            .get(part as usize - 1)
+            .map(String::as_str)
+    }
+
+    /// Every (profile, day, answers) entry, ordered by profile then day.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u8, &[String])> {
+        self.profiles.iter().flat_map(|(profile, days)| {
+            days.iter()
+                .map(move |(&day, parts)| (profile.as_str(), day, parts.as_slice()))
+        })
+    }
+}
+
+fn is_same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+[default]
+day1 = [1029, 5892]
+day12 = [569]
+
+[alice]
+day11 = ["607", 506264456238938]
+"#;
+
+    #[test]
+    pub fn parse_test() {
+        let answers = Answers::parse(SAMPLE).unwrap();
+
+        assert_eq!(answers.get("default", 1, Part::Two), Some("5892"));
+        assert_eq!(answers.get("default", 12, Part::Two), None);
+        assert_eq!(answers.get("alice", 11, Part::One), Some("607"));
+        assert_eq!(answers.iter().count(), 3);
+    }
+
+    #[test]
+    pub fn parse_error_test() {
+        assert!(Answers::parse("[default]\nday1 = [1, 2, 3]").is_err());
+        assert!(Answers::parse("[default]\none = [1]").is_err());
+        assert!(Answers::parse("[default]\nday1 = [1.5]").is_err());
+    }
+
+    #[test]
+    pub fn merge_test() {
+        let mut answers = Answers::parse(SAMPLE).unwrap();
+        answers.merge(Answers::parse("[default]\nday1 = [1]").unwrap());
+
+        assert_eq!(answers.get("default", 1, Part::One), Some("1"));
+        assert_eq!(answers.get("default", 1, Part::Two), None);
+        assert_eq!(answers.get("default", 12, Part::One), Some("569"));
+    }
+
+    #[test]
+    pub fn load_all_test() {
+        let dir = env::temp_dir().join(format!("aoc-answers-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(ANSWERS_FILE), "[default]\nday2 = [7]").unwrap();
+
+        // Only the directory's own answers, not the repo's for its inputs
+        if env::var_os("AOC_ANSWERS").is_none() {
+            let answers = Answers::load_all(&dir).unwrap();
+            assert_eq!(answers, Answers::parse("[default]\nday2 = [7]").unwrap());
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,153 @@
+//! Timing baselines: a run's timings saved as JSON, so later runs can be
+//! checked for slowdowns.
+//!
+//! ```json
+//! { "timings": { "day7/generator": 10250, "day7/part1": 4810, "bob/day7/part1": 4902 } }
+//! ```
+
+use std::{collections::BTreeMap, fmt, path::Path, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{inputs::DEFAULT_PROFILE, solution::Part};
+
+/// Slowdowns smaller than this are timer noise, whatever the percentage.
+pub const NOISE_FLOOR: Duration = Duration::from_micros(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Generator,
+    Part(Part),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Stage::Generator => f.pad("generator"),
+            Stage::Part(part) => f.pad(&format!("part{part}")),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Nanoseconds, keyed by [`Baseline::key`].
+    timings: BTreeMap<String, u64>,
+}
+
+impl Baseline {
+    /// `day7/part1`, prefixed with the profile unless it is the default one.
+    pub fn key(profile: Option<&str>, day: u8, stage: Stage) -> String {
+        match profile.filter(|&profile| profile != DEFAULT_PROFILE) {
+            Some(profile) => format!("{profile}/day{day}/{stage}"),
+            None => format!("day{day}/{stage}"),
+        }
+    }
+
+    pub fn insert(&mut self, key: String, elapsed: Duration) {
+        let nanos = u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+        self.timings.insert(key, nanos);
+    }
+
+    pub fn get(&self, key: &str) -> Option<Duration> {
+        self.timings.get(key).copied().map(Duration::from_nanos)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        serde_json::from_str(&json).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json + "\n").map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Every timing in `current` more than `threshold` percent slower than
+    /// this baseline. Timings missing from either side are not compared.
+    pub fn regressions(&self, current: &Baseline, threshold: f64) -> Vec<Regression> {
+        current
+            .timings
+            .keys()
+            .filter_map(|key| {
+                let regression = Regression {
+                    key: key.clone(),
+                    baseline: self.get(key)?,
+                    current: current.get(key)?,
+                };
+
+                (regression.current > regression.baseline + NOISE_FLOOR
+                    && regression.slowdown() > threshold)
+                    .then_some(regression)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regression {
+    pub key: String,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    /// How much slower the current timing is, in percent.
+    pub fn slowdown(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:?} against a baseline of {:?} ({:+.1}%)",
+            self.key,
+            self.current,
+            self.baseline,
+            self.slowdown()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn key_test() {
+        assert_eq!(Baseline::key(None, 7, Stage::Generator), "day7/generator");
+        assert_eq!(
+            Baseline::key(Some("default"), 7, Stage::Part(Part::One)),
+            "day7/part1"
+        );
+        assert_eq!(
+            Baseline::key(Some("bob"), 7, Stage::Part(Part::Two)),
+            "bob/day7/part2"
+        );
+    }
+
+    #[test]
+    pub fn regressions_test() {
+        let mut baseline = Baseline::default();
+        baseline.insert("day1/part1".to_string(), Duration::from_micros(100));
+        baseline.insert("day1/part2".to_string(), Duration::from_micros(100));
+        baseline.insert("day2/part1".to_string(), Duration::from_nanos(100));
+
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+
+        let mut current = Baseline::default();
+        current.insert("day1/part1".to_string(), Duration::from_micros(150));
+        current.insert("day1/part2".to_string(), Duration::from_micros(105));
+        // Three times slower, but still under the noise floor
+        current.insert("day2/part1".to_string(), Duration::from_nanos(300));
+        current.insert("day3/part1".to_string(), Duration::from_micros(100));
+
+        let regressions = baseline.regressions(&current, 10.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].key, "day1/part1");
+        assert_eq!(regressions[0].slowdown().round(), 50.0);
+    }
+}
@@ -0,0 +1,659 @@
+//! Runs solutions without cargo-aoc.
+//!
+//! ```text
+//! runner <DAY|all> [--part 1|2] [--input PATH|DIR|-] [--samples N] [--json]
+//!        [--sequential] [--save-baseline FILE] [--baseline FILE [--threshold PCT]]
+//!        [--trace text|json]
+//! ```
+//!
+//! Inputs default to `input/2025/dayN.txt` (or `$AOC_INPUT_DIR/dayN.txt`), and
+//! `--input -` reads from stdin. Given a directory, every profile in it
+//! (`DIR/dayN.txt` and `DIR/<profile>/dayN.txt`) is run and summarized in a
+//! table. Answers are checked against the answers manifest wherever the
+//! input's profile is known; a directory other than the input directory is
+//! only checked against its own `answers.toml`.
+//!
+//! Jobs run in parallel on the rayon pool, and are reported in order.
+//! `--sequential` runs them one at a time instead, so they don't compete for
+//! cores and caches; comparing against a baseline always does.
+//!
+//! Each timing is the fastest of `--samples` runs. `--save-baseline` writes
+//! the timings as JSON, and `--baseline` fails the run if any timing is more
+//! than `--threshold` percent (default 10) slower than the saved one.
+//!
+//! `--json` prints a JSON array instead, with one record per solved part:
+//!
+//! ```json
+//! {"profile": "default", "day": 11, "part": 2, "answer": "506264456238938",
+//!  "answer_type": "unsigned", "expected": "506264456238938", "correct": true,
+//!  "generator_ns": 210375, "solve_ns": 48208, "input_hash": "9d5b0e4cd2b8a4f1"}
+//! ```
+//!
+//! Answers are strings, since many exceed what a JSON number holds exactly.
+//! A job that failed is recorded as `{"profile", "day", "error"}`.
+//!
+//! `--trace` runs each job once more, untimed, and prints the steps its
+//! solver recorded to stderr, as text or as JSON lines:
+//!
+//! ```text
+//! day1/part1 rotate clicks=-68 position=82 landed=false passes=1
+//! {"profile":"default","day":1,"stage":"part1","event":"rotate","clicks":-68,"position":82,"landed":false,"passes":1}
+//! ```
+//!
+//! Solvers only record steps when built with the `trace` feature.
+//!
+//! Built with the `alloc-stats` feature, the runner also counts the
+//! allocations, bytes allocated and peak live bytes of each generator and
+//! part, in another untimed run. Reports show them after the timings, and
+//! JSON records gain `generator_alloc` and `solve_alloc` objects:
+//!
+//! ```json
+//! {"allocations": 3, "bytes": 47104, "peak_bytes": 32768}
+//! ```
+
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use advent_of_code_2025::{
+    self as aoc,
+    alloc_stats::{self, AllocStats},
+    answers::Answers,
+    baseline::{Baseline, Stage},
+    error::SolveError,
+    inputs,
+    solution::{Answer, Part},
+    trace::{self, Step},
+};
+use rayon::prelude::*;
+use serde::Serialize;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOC: alloc_stats::CountingAlloc = alloc_stats::CountingAlloc;
+
+const USAGE: &str = "usage: runner <DAY|all> [--part 1|2] [--input PATH|DIR|-] [--samples N] \
+                     [--json] [--sequential] [--save-baseline FILE] [--baseline FILE [--threshold PCT]] \
+                     [--trace text|json]";
+
+struct Args {
+    days: Vec<u8>,
+    part: Option<Part>,
+    input: Option<String>,
+    samples: usize,
+    json: bool,
+    sequential: bool,
+    save_baseline: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    trace: Option<TraceFormat>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TraceFormat {
+    Text,
+    Json,
+}
+
+enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+struct Job {
+    /// Only known when the input came from an input directory.
+    profile: Option<String>,
+    day: u8,
+    source: Source,
+}
+
+struct Report {
+    input_hash: u64,
+    generator: Duration,
+    parts: Vec<(Part, Answer, Duration)>,
+    trace: Vec<(Stage, Step)>,
+    /// Only measured with the `alloc-stats` feature.
+    allocs: Vec<(Stage, AllocStats)>,
+}
+
+impl Report {
+    fn allocs(&self, stage: Stage) -> Option<AllocStats> {
+        self.allocs
+            .iter()
+            .find(|(s, _)| *s == stage)
+            .map(|&(_, stats)| stats)
+    }
+}
+
+struct Outcome {
+    job: Job,
+    report: Result<Report, String>,
+}
+
+/// Calls `f` `samples` times, returning the last result and the fastest time.
+fn time<T>(samples: usize, mut f: impl FnMut() -> T) -> (T, Duration) {
+    let mut res = None;
+    let mut fastest = Duration::MAX;
+
+    for _ in 0..samples.max(1) {
+        let start = Instant::now();
+        let value = f();
+        fastest = fastest.min(start.elapsed());
+        res = Some(value);
+    }
+
+    (res.expect("at least one sample"), fastest)
+}
+
+fn run(
+    day: u8,
+    part: Option<Part>,
+    samples: usize,
+    trace: bool,
+    input: &str,
+) -> Result<Report, SolveError> {
+    let solution = aoc::solution(day).ok_or(SolveError::UnknownDay(day))?;
+    let (parsed, generator) = time(samples, || solution.parse(input));
+    let parsed = parsed?;
+
+    let parts = Part::ALL
+        .into_iter()
+        .filter(|&p| part.is_none_or(|part| part == p))
+        .map(|p| {
+            let (answer, elapsed) = time(samples, || solution.solve(&parsed, p));
+            (p, answer, elapsed)
+        })
+        .filter(|(p, answer, _)| part == Some(*p) || *answer != Answer::Unsolved)
+        .collect::<Vec<_>>();
+
+    let mut steps = Vec::new();
+    if trace {
+        let (_, generator) = trace::capture(|| solution.parse(input));
+        steps.extend(generator.into_iter().map(|step| (Stage::Generator, step)));
+        for &(p, _, _) in &parts {
+            let (_, part) = trace::capture(|| solution.solve(&parsed, p));
+            steps.extend(part.into_iter().map(|step| (Stage::Part(p), step)));
+        }
+    }
+
+    let mut allocs = Vec::new();
+    if cfg!(feature = "alloc-stats") {
+        let (_, stats) = alloc_stats::measure(|| solution.parse(input));
+        allocs.push((Stage::Generator, stats));
+        for &(p, _, _) in &parts {
+            let (_, stats) = alloc_stats::measure(|| solution.solve(&parsed, p));
+            allocs.push((Stage::Part(p), stats));
+        }
+    }
+
+    Ok(Report {
+        input_hash: inputs::hash(input),
+        generator,
+        parts,
+        trace: steps,
+        allocs,
+    })
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = std::env::args().skip(1);
+    let mut days = None;
+    let mut part = None;
+    let mut input = None;
+    let mut samples = 1;
+    let mut json = false;
+    let mut sequential = false;
+    let mut save_baseline = None;
+    let mut baseline = None;
+    let mut threshold = 10.0;
+    let mut trace = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} requires a value"));
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(value()?.parse()?),
+            "--input" | "-i" => input = Some(value()?),
+            "--samples" | "-n" => {
+                samples = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&n| n > 0)
+                    .ok_or("--samples must be a positive number")?
+            }
+            "--json" => json = true,
+            "--sequential" => sequential = true,
+            "--save-baseline" => save_baseline = Some(value()?.into()),
+            "--baseline" => baseline = Some(value()?.into()),
+            "--threshold" => {
+                threshold = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&pct: &f64| pct >= 0.0)
+                    .ok_or("--threshold must be a percentage")?
+            }
+            "--trace" => {
+                trace = Some(match value()?.as_str() {
+                    "text" => TraceFormat::Text,
+                    "json" => TraceFormat::Json,
+                    format => return Err(format!("unknown trace format: {format}")),
+                });
+                if !cfg!(feature = "trace") {
+                    return Err("--trace needs a build with the trace feature".to_string());
+                }
+            }
+            "all" if days.is_none() => {
+                days = Some(aoc::SOLUTIONS.iter().map(|s| s.day()).collect())
+            }
+            _ if days.is_none() => {
+                let day = arg
+                    .strip_prefix("day")
+                    .unwrap_or(&arg)
+                    .parse()
+                    .map_err(|_| format!("invalid day: {arg}"))?;
+                days = Some(vec![day]);
+            }
+            _ => return Err(format!("unexpected argument: {arg}")),
+        }
+    }
+
+    let days: Vec<u8> = days.ok_or("missing day")?;
+    if days.len() > 1
+        && input
+            .as_deref()
+            .is_some_and(|input| !Path::new(input).is_dir())
+    {
+        return Err("--input can only be a file with a single day".to_string());
+    }
+
+    Ok(Args {
+        days,
+        part,
+        input,
+        samples,
+        json,
+        sequential,
+        save_baseline,
+        baseline,
+        threshold,
+        trace,
+    })
+}
+
+/// The jobs to run, and the input directory whose answers they are checked against.
+fn jobs(args: &Args) -> Result<(Vec<Job>, PathBuf), String> {
+    let job = |profile: Option<&str>, day, source| Job {
+        profile: profile.map(String::from),
+        day,
+        source,
+    };
+
+    let dir = match args.input.as_deref() {
+        Some("-") => {
+            let jobs = vec![job(None, args.days[0], Source::Stdin)];
+            return Ok((jobs, inputs::input_dir()));
+        }
+        Some(path) if !Path::new(path).is_dir() => {
+            let jobs = vec![job(None, args.days[0], Source::File(path.into()))];
+            return Ok((jobs, inputs::input_dir()));
+        }
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let dir = inputs::input_dir();
+            let jobs = args
+                .days
+                .iter()
+                .map(|&day| {
+                    let path = inputs::input_path(&dir, day);
+                    job(Some(inputs::DEFAULT_PROFILE), day, Source::File(path))
+                })
+                .collect();
+            return Ok((jobs, dir));
+        }
+    };
+
+    let profiles = inputs::profiles(&dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    let mut jobs = Vec::new();
+    for profile in &profiles {
+        let profile_dir = inputs::profile_dir(&dir, profile);
+        for &day in &args.days {
+            let path = inputs::input_path(&profile_dir, day);
+            if path.exists() {
+                jobs.push(job(Some(profile), day, Source::File(path)));
+            }
+        }
+    }
+
+    Ok((jobs, dir))
+}
+
+fn read_input(source: &Source) -> Result<String, String> {
+    match source {
+        Source::Stdin => {
+            let mut buf = String::new();
+            std::io::stdin()
+                .read_to_string(&mut buf)
+                .map_err(|e| format!("stdin: {e}"))?;
+            Ok(inputs::normalize(&buf).into_owned())
+        }
+        Source::File(path) => {
+            inputs::read_input(path).map_err(|e| format!("{}: {e}", path.display()))
+        }
+    }
+}
+
+fn execute(job: Job, args: &Args) -> Outcome {
+    let report = read_input(&job.source).and_then(|input| {
+        run(
+            job.day,
+            args.part,
+            args.samples,
+            args.trace.is_some(),
+            &input,
+        )
+        .map_err(|e| e.to_string())
+    });
+
+    Outcome { job, report }
+}
+
+/// Compares an answer with the manifest, or `None` when the right answer is unknown.
+fn check(answers: &Answers, job: &Job, part: Part, answer: &Answer) -> Option<Result<(), String>> {
+    let expected = answers.get(job.profile.as_deref()?, job.day, part)?;
+
+    Some(if answer.to_string() == expected {
+        Ok(())
+    } else {
+        Err(expected.to_string())
+    })
+}
+
+fn print_reports(outcomes: &[Outcome], answers: &Answers) {
+    for Outcome { job, report } in outcomes {
+        let day = job.day;
+        match report {
+            Ok(report) => {
+                let allocs = |stage| match report.allocs(stage) {
+                    Some(stats) => format!("  {stats}"),
+                    None => String::new(),
+                };
+
+                println!("Day {day}");
+                println!(
+                    "  {:<30} {:>12?}{}",
+                    "generator",
+                    report.generator,
+                    allocs(Stage::Generator)
+                );
+                for (part, answer, elapsed) in &report.parts {
+                    let mark = match check(answers, job, *part, answer) {
+                        Some(Err(expected)) => format!(" (expected {expected})"),
+                        _ => String::new(),
+                    };
+                    println!(
+                        "  {:<30} {elapsed:>12?}{}",
+                        format!("part {part}: {answer}{mark}"),
+                        allocs(Stage::Part(*part))
+                    );
+                }
+            }
+            Err(e) => eprintln!("Day {day}: {e}"),
+        }
+    }
+}
+
+fn print_table(outcomes: &[Outcome], answers: &Answers) {
+    let cell = |job, part, report: &Report| {
+        let (_, answer, _) = report.parts.iter().find(|(p, _, _)| *p == part)?;
+        Some(match check(answers, job, part, answer) {
+            Some(Ok(())) => format!("{answer} ok"),
+            Some(Err(_)) => format!("{answer} WRONG"),
+            None => answer.to_string(),
+        })
+    };
+
+    println!("{:<16} {:>4}  {:<24} part 2", "profile", "day", "part 1");
+    for Outcome { job, report } in outcomes {
+        let profile = job.profile.as_deref().unwrap_or("-");
+        let (part1, part2) = match report {
+            Ok(report) => (
+                cell(job, Part::One, report).unwrap_or_default(),
+                cell(job, Part::Two, report).unwrap_or_default(),
+            ),
+            Err(_) => ("error".to_string(), String::new()),
+        };
+        let row = format!("{profile:<16} {:>4}  {part1:<24} {part2}", job.day);
+        println!("{}", row.trim_end());
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Record<'a> {
+    Solved {
+        profile: Option<&'a str>,
+        day: u8,
+        part: u8,
+        answer: String,
+        answer_type: &'static str,
+        expected: Option<&'a str>,
+        correct: Option<bool>,
+        generator_ns: u64,
+        solve_ns: u64,
+        input_hash: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        generator_alloc: Option<AllocStats>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        solve_alloc: Option<AllocStats>,
+    },
+    Failed {
+        profile: Option<&'a str>,
+        day: u8,
+        error: &'a str,
+    },
+}
+
+fn print_json(outcomes: &[Outcome], answers: &Answers) {
+    let nanos = |elapsed: Duration| u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX);
+    let mut records = Vec::new();
+
+    for Outcome { job, report } in outcomes {
+        let profile = job.profile.as_deref();
+        match report {
+            Ok(report) => {
+                records.extend(report.parts.iter().map(|(part, answer, elapsed)| {
+                    let check = check(answers, job, *part, answer);
+                    Record::Solved {
+                        profile,
+                        day: job.day,
+                        part: *part as u8,
+                        answer: answer.to_string(),
+                        answer_type: answer.kind(),
+                        expected: profile.and_then(|profile| answers.get(profile, job.day, *part)),
+                        correct: check.map(|check| check.is_ok()),
+                        generator_ns: nanos(report.generator),
+                        solve_ns: nanos(*elapsed),
+                        input_hash: format!("{:016x}", report.input_hash),
+                        generator_alloc: report.allocs(Stage::Generator),
+                        solve_alloc: report.allocs(Stage::Part(*part)),
+                    }
+                }));
+            }
+            Err(error) => records.push(Record::Failed {
+                profile,
+                day: job.day,
+                error,
+            }),
+        }
+    }
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&records).expect("records are serializable")
+    );
+}
+
+#[derive(Serialize)]
+struct TraceRecord<'a> {
+    profile: Option<&'a str>,
+    day: u8,
+    stage: String,
+    #[serde(flatten)]
+    step: &'a Step,
+}
+
+/// Every recorded step, one line each, on stderr.
+fn print_traces(outcomes: &[Outcome], format: TraceFormat) {
+    for Outcome { job, report } in outcomes {
+        let Ok(report) = report else { continue };
+        let profile = job.profile.as_deref();
+
+        for (stage, step) in &report.trace {
+            match format {
+                TraceFormat::Text => {
+                    eprintln!("{} {step}", Baseline::key(profile, job.day, *stage));
+                }
+                TraceFormat::Json => {
+                    let record = TraceRecord {
+                        profile,
+                        day: job.day,
+                        stage: stage.to_string(),
+                        step,
+                    };
+                    eprintln!(
+                        "{}",
+                        serde_json::to_string(&record).expect("steps are serializable")
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn timings(outcomes: &[Outcome]) -> Baseline {
+    let mut timings = Baseline::default();
+
+    for Outcome { job, report } in outcomes {
+        let Ok(report) = report else { continue };
+        let key = |stage| Baseline::key(job.profile.as_deref(), job.day, stage);
+
+        timings.insert(key(Stage::Generator), report.generator);
+        for (part, _, elapsed) in &report.parts {
+            timings.insert(key(Stage::Part(*part)), *elapsed);
+        }
+    }
+
+    timings
+}
+
+/// Every failed job and wrong answer, one line each.
+fn problems(outcomes: &[Outcome], answers: &Answers) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for Outcome { job, report } in outcomes {
+        let name = match &job.profile {
+            Some(profile) => format!("{profile} day{}", job.day),
+            None => format!("day{}", job.day),
+        };
+
+        match report {
+            Ok(report) => {
+                for (part, answer, _) in &report.parts {
+                    if let Some(Err(expected)) = check(answers, job, *part, answer) {
+                        problems.push(format!(
+                            "{name} part {part}: expected {expected}, got {answer}"
+                        ));
+                    }
+                }
+            }
+            Err(e) => problems.push(format!("{name}: {e}")),
+        }
+    }
+
+    problems
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let setup = jobs(&args).and_then(|(jobs, dir)| {
+        let baseline = args.baseline.as_deref().map(Baseline::load).transpose()?;
+        Ok((jobs, Answers::load_all(&dir)?, baseline))
+    });
+    let (jobs, answers, baseline) = match setup {
+        Ok(res) => res,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let sequential = args.sequential || args.save_baseline.is_some() || baseline.is_some();
+    let start = Instant::now();
+    let outcomes: Vec<_> = if sequential {
+        jobs.into_iter().map(|job| execute(job, &args)).collect()
+    } else {
+        jobs.into_par_iter()
+            .map(|job| execute(job, &args))
+            .collect()
+    };
+    let elapsed = start.elapsed();
+
+    let table = args
+        .input
+        .as_deref()
+        .is_some_and(|input| Path::new(input).is_dir());
+    if args.json {
+        print_json(&outcomes, &answers);
+    } else if table {
+        print_table(&outcomes, &answers);
+    } else {
+        print_reports(&outcomes, &answers);
+    }
+    if let Some(format) = args.trace {
+        print_traces(&outcomes, format);
+    }
+    if !args.json && outcomes.len() > 1 {
+        let mode = if sequential {
+            "sequentially"
+        } else {
+            "in parallel"
+        };
+        println!("\n{} jobs in {elapsed:?}, run {mode}", outcomes.len());
+    }
+
+    let mut problems = problems(&outcomes, &answers);
+    let timings = timings(&outcomes);
+    if let Some(baseline) = baseline {
+        problems.extend(
+            baseline
+                .regressions(&timings, args.threshold)
+                .iter()
+                .map(|regression| format!("slower than the baseline: {regression}")),
+        );
+    }
+    if let Some(path) = &args.save_baseline
+        && let Err(e) = timings.save(path)
+    {
+        problems.push(e);
+    }
+
+    if problems.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+
+    eprintln!();
+    for problem in &problems {
+        eprintln!("{problem}");
+    }
+    ExitCode::FAILURE
+}
@@ -0,0 +1,135 @@
+//! Starts a new day from `src/template.rs`.
+//!
+//! ```text
+//! scaffold <DAY> <TITLE>
+//! ```
+//!
+//! Writes `src/dayNN.rs`, registers it in `src/lib.rs` and links it from
+//! `README.md`. An existing day is never overwritten.
+
+use std::{fs, io::Write, path::Path, process::ExitCode};
+
+const LIB_MARKER: &str = "// Insert before";
+const REGISTRY_MARKER: &str = "// Insert solution before";
+const README_MARKER: &str = "<!-- Insert before -->";
+
+/// The year from `aoc_lib! { year = 2025 }`.
+fn year(lib: &str) -> Result<u16, String> {
+    lib.lines()
+        .find_map(|line| {
+            let rest = line.trim().strip_prefix("aoc_lib!")?;
+            let rest = rest.trim().strip_prefix('{')?.trim().strip_prefix("year")?;
+            let rest = rest.trim().strip_prefix('=')?.trim();
+            let end = rest.find(|c: char| !c.is_ascii_digit())?;
+            rest[..end].parse().ok()
+        })
+        .ok_or_else(|| "could not find the year in aoc_lib!".to_string())
+}
+
+fn instantiate(template: &str, day: u8) -> String {
+    template
+        .replace("dayN", &format!("day{day}"))
+        .replace("DAY: u8 = N;", &format!("DAY: u8 = {day};"))
+}
+
+/// Inserts `line` on its own line before the line containing `marker`,
+/// matching the marker's indentation.
+fn insert_before(text: &str, marker: &str, line: &str) -> Result<String, String> {
+    let at = text
+        .find(marker)
+        .ok_or_else(|| format!("could not find {marker:?}"))?;
+    let line_start = text[..at].rfind('\n').map_or(0, |i| i + 1);
+    let indent = &text[line_start..at];
+
+    Ok(format!(
+        "{}{indent}{line}\n{}",
+        &text[..line_start],
+        &text[line_start..]
+    ))
+}
+
+fn scaffold(root: &Path, day: u8, title: &str) -> Result<(), String> {
+    let read = |path: &str| fs::read_to_string(root.join(path)).map_err(|e| format!("{path}: {e}"));
+
+    let module = format!("day{day:02}");
+    let lib = read("src/lib.rs")?;
+    if lib.contains(&format!("pub mod {module};")) {
+        return Err(format!("{module} is already registered in src/lib.rs"));
+    }
+
+    let year = year(&lib)?;
+    let lib = insert_before(&lib, LIB_MARKER, &format!("pub mod {module};"))?;
+    let lib = insert_before(&lib, REGISTRY_MARKER, &format!("&{module}::Solver,"))?;
+
+    let readme = insert_before(
+        &read("README.md")?,
+        README_MARKER,
+        &format!(
+            "- [Day {day}: {title}](https://adventofcode.com/{year}/day/{day})\n  - [solution](src/{module}.rs)"
+        ),
+    )?;
+
+    let source = instantiate(&read("src/template.rs")?, day);
+    let path = format!("src/{module}.rs");
+    fs::File::create_new(root.join(&path))
+        .and_then(|mut file| file.write_all(source.as_bytes()))
+        .map_err(|e| format!("{path}: {e}"))?;
+
+    fs::write(root.join("src/lib.rs"), lib).map_err(|e| format!("src/lib.rs: {e}"))?;
+    fs::write(root.join("README.md"), readme).map_err(|e| format!("README.md: {e}"))?;
+
+    println!("created {path} for day {day} of {year}: {title}");
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let day = args
+        .next()
+        .and_then(|day| day.parse().ok())
+        .filter(|day| (1..=25).contains(day));
+    let title = args.collect::<Vec<_>>().join(" ");
+
+    let Some(day) = day.filter(|_| !title.is_empty()) else {
+        eprintln!("usage: scaffold <DAY> <TITLE>");
+        return ExitCode::FAILURE;
+    };
+
+    match scaffold(Path::new(env!("CARGO_MANIFEST_DIR")), day, &title) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn year_test() {
+        assert_eq!(year("pub mod day01;\naoc_lib! { year = 2025 }\n"), Ok(2025));
+        assert!(year("pub mod day01;\n").is_err());
+    }
+
+    #[test]
+    pub fn instantiate_test() {
+        let template = "#[aoc_generator(dayN)]\n#[aoc(dayN, part1)]\n    const DAY: u8 = N;\n";
+        assert_eq!(
+            instantiate(template, 13),
+            "#[aoc_generator(day13)]\n#[aoc(day13, part1)]\n    const DAY: u8 = 13;\n"
+        );
+    }
+
+    #[test]
+    pub fn insert_before_test() {
+        let text = "a\n    // Insert solution before\n];\n";
+        assert_eq!(
+            insert_before(text, REGISTRY_MARKER, "&day13::Solver,"),
+            Ok("a\n    &day13::Solver,\n    // Insert solution before\n];\n".to_string())
+        );
+        assert!(insert_before(text, README_MARKER, "x").is_err());
+    }
+}
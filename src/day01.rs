@@ -1,59 +1,589 @@
+use std::{
+    cmp::Reverse,
+    fmt,
+    marker::PhantomData,
+    ops::{Add, Range, Sub},
+};
+
 use aoc_runner_derive::{aoc, aoc_generator};
+use serde::Serialize;
 
-use crate::common::parse::parse_lines_fn;
+use crate::{
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Part, Solution},
+    trace,
+};
 
-const DIAL_SIZE: i32 = 100;
-const DIAL_START: i32 = 50;
+const DIAL_SIZE: i8 = 100;
+const DIAL_START: i8 = 50;
 
-fn parse_input(line: &str) -> i32 {
-    let (b, num) = line.split_at(1);
-    let num: i32 = num.parse().unwrap();
+/// A signed integer type to count clicks in. Dials never overflow, whatever
+/// the size of their rotations, so only parsing cares how wide it is.
+pub trait Clicks:
+    Copy
+    + Ord
+    + fmt::Debug
+    + Serialize
+    + From<i8>
+    + Into<i128>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+{
+    fn div_euclid(self, rhs: Self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn unsigned_abs(self) -> u128;
 
-    if "L" == b { -num } else { num }
+    /// `n` clicks to the right, and `-n` to the left, if they fit.
+    fn from_magnitude(n: u128, left: bool) -> Option<Self>;
 }
 
-#[aoc_generator(day1)]
-pub fn generator(input: &str) -> Vec<i32> {
-    parse_lines_fn(input, parse_input)
+macro_rules! impl_clicks {
+    ($($t:ty),+) => {
+        $(
+            impl Clicks for $t {
+                fn div_euclid(self, rhs: Self) -> Self {
+                    <$t>::div_euclid(self, rhs)
+                }
+
+                fn rem_euclid(self, rhs: Self) -> Self {
+                    <$t>::rem_euclid(self, rhs)
+                }
+
+                fn unsigned_abs(self) -> u128 {
+                    <$t>::unsigned_abs(self).into()
+                }
+
+                fn from_magnitude(n: u128, left: bool) -> Option<Self> {
+                    let n = <$t>::try_from(n).ok()?;
+                    Some(if left { -n } else { n })
+                }
+            }
+        )+
+    };
 }
 
-#[aoc(day1, part1)]
-pub fn part1(spins: &[i32]) -> usize {
-    let mut dial = DIAL_START;
-    let mut count = 0;
+impl_clicks!(i32, i64, i128);
 
-    for x in spins {
-        dial = (dial + x).rem_euclid(DIAL_SIZE);
+/// A dial numbered `0..size`, turned by rotations of signed clicks: left
+/// is negative, right positive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial<T = i32> {
+    size: T,
+    position: T,
+}
+
+/// What one rotation did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step<T = i32> {
+    pub clicks: T,
+    /// Where the dial points afterwards.
+    pub position: T,
+    /// Whether it ended up pointing at 0.
+    pub landed: bool,
+    /// How many clicks left it pointing at 0, the last one included. A
+    /// `u128`, since a rotation of `i128::MIN` on a dial of 1 passes it
+    /// more often than an `i128` holds.
+    pub passes: u128,
+    /// How many times it wrapped round, from `size - 1` to 0 when turning
+    /// right, and negative when turning left from 0 to `size - 1`.
+    pub carry: T,
+}
 
-        if dial == 0 {
-            count += 1;
+impl<T: Clicks> Dial<T> {
+    /// # Panics
+    ///
+    /// If `size` isn't positive, or `start` isn't in `0..size`.
+    pub fn new(size: T, start: T) -> Self {
+        let zero = T::from(0);
+        assert!(size > zero, "expected a positive dial size");
+        assert!(
+            (zero..size).contains(&start),
+            "expected a start in 0..{size:?}"
+        );
+
+        Dial {
+            size,
+            position: start,
         }
     }
 
-    count
+    pub fn size(&self) -> T {
+        self.size
+    }
+
+    pub fn position(&self) -> T {
+        self.position
+    }
+
+    pub fn rotate(&mut self, clicks: T) -> Step<T> {
+        let (zero, one) = (T::from(0), T::from(1));
+
+        // Whole turns, and then less than one more, which wraps round if it
+        // reaches size. Nothing here can overflow, unlike position + clicks.
+        let turns = clicks.div_euclid(self.size);
+        let rest = clicks.rem_euclid(self.size);
+        let to_wrap = self.size - self.position;
+        let (position, carry) = if rest >= to_wrap {
+            (rest - to_wrap, turns + one)
+        } else {
+            (self.position + rest, turns)
+        };
+
+        // Every multiple of size on the way. Turning left, that's 0 itself
+        // too if it started elsewhere, but not where it stops unless that's 0.
+        let passes = if clicks >= zero {
+            carry.unsigned_abs()
+        } else {
+            carry.unsigned_abs() + u128::from(self.position != zero) - u128::from(position != zero)
+        };
+        self.position = position;
+
+        let step = Step {
+            clicks,
+            position,
+            landed: position == zero,
+            passes,
+            carry,
+        };
+        trace!(
+            "rotate",
+            clicks = clicks,
+            position = position,
+            landed = step.landed,
+            passes = passes
+        );
+        step
+    }
+
+    /// Applies every rotation in turn, yielding what each one did.
+    pub fn simulate<I: IntoIterator<Item = T>>(
+        mut self,
+        spins: I,
+    ) -> impl Iterator<Item = Step<T>> {
+        spins.into_iter().map(move |clicks| self.rotate(clicks))
+    }
 }
 
-#[aoc(day1, part2)]
-pub fn part2(spins: &[i32]) -> usize {
-    let mut dial = DIAL_START;
-    let mut count = 0;
+/// The puzzle's dial, numbered 0 to 99 and starting at 50.
+impl<T: Clicks> Default for Dial<T> {
+    fn default() -> Self {
+        Dial::new(T::from(DIAL_SIZE), T::from(DIAL_START))
+    }
+}
+
+/// Prefix sums over a log of rotations, so the zeros in any stretch of it
+/// can be counted in O(1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZeroCrossingIndex<T = i32> {
+    /// Where the dial points after the first `k` rotations, the start at 0.
+    positions: Vec<T>,
+    /// Landings on 0 in the first `k` rotations.
+    landed: Vec<usize>,
+    /// Clicks pointing at 0 in the first `k` rotations.
+    passes: Vec<u128>,
+}
+
+impl<T: Clicks> ZeroCrossingIndex<T> {
+    /// Returns `None` if the rotations leave the dial at 0 more often than a
+    /// `u128` holds.
+    pub fn new(dial: Dial<T>, spins: &[T]) -> Option<Self> {
+        let mut positions = Vec::with_capacity(spins.len() + 1);
+        let mut landed = Vec::with_capacity(spins.len() + 1);
+        let mut passes = Vec::with_capacity(spins.len() + 1);
+        positions.push(dial.position());
+        landed.push(0);
+        passes.push(0u128);
+
+        for step in dial.simulate(spins.iter().copied()) {
+            positions.push(step.position);
+            landed.push(landed[landed.len() - 1] + usize::from(step.landed));
+            passes.push(passes[passes.len() - 1].checked_add(step.passes)?);
+        }
+
+        Some(ZeroCrossingIndex {
+            positions,
+            landed,
+            passes,
+        })
+    }
+
+    /// The number of rotations.
+    pub fn len(&self) -> usize {
+        self.positions.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Where the dial points after the first `k` rotations.
+    pub fn position(&self, k: usize) -> T {
+        self.positions[k]
+    }
+
+    /// How many of the rotations in `range` land on 0.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is reversed or reaches past the last rotation.
+    pub fn landed(&self, range: Range<usize>) -> usize {
+        assert!(range.start <= range.end, "expected a range, got {range:?}");
+        self.landed[range.end] - self.landed[range.start]
+    }
+
+    /// How many clicks of the rotations in `range` leave the dial at 0.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is reversed or reaches past the last rotation.
+    pub fn passes(&self, range: Range<usize>) -> u128 {
+        assert!(range.start <= range.end, "expected a range, got {range:?}");
+        self.passes[range.end] - self.passes[range.start]
+    }
+}
+
+/// What a log of rotations counts from every start position at once: landings
+/// on 0 for part 1, and clicks at 0 for part 2.
+///
+/// Starting at `s` instead of 0 shifts every position by `s`, so rather than
+/// simulating each start, this works from where the rotations would leave a
+/// dial that started at 0, in O(rotations + size).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartCounts<T = i32> {
+    landed: Vec<usize>,
+    passes: Vec<usize>,
+    start: PhantomData<T>,
+}
+
+/// Where `a + r` ends up on a dial of size `n`, for `a` and `r` in `0..n`,
+/// and whether it wrapped round to get there. Nothing here can overflow.
+fn wrap(n: usize, a: usize, r: usize) -> (bool, usize) {
+    if r >= n - a {
+        (true, r - (n - a))
+    } else {
+        (false, a + r)
+    }
+}
+
+impl<T: Clicks> StartCounts<T> {
+    /// Returns `None` if some start leaves the dial at 0 more often than a
+    /// `usize` holds.
+    ///
+    /// # Panics
+    ///
+    /// If `size` isn't positive, or doesn't fit a `usize`.
+    pub fn new(size: T, spins: &[T]) -> Option<Self> {
+        let zero = T::from(0);
+        assert!(size > zero, "expected a positive dial size");
+        let n = usize::try_from(size.unsigned_abs()).expect("expected a dial size to fit a usize");
+
+        let mut landed = vec![0; n];
+        // The passes of start s are base + the sum of steps[..=s]
+        let mut base = 0i128;
+        let mut steps = vec![0i128; n + 1];
+        // floor((k * n + m + s) / n) for every s in 0..n, where m is in 0..n:
+        // k, stepping up by one where m + s reaches n
+        let mut add_floor = |k: i128, m: usize, sign: i128| {
+            base = base.checked_add(k.checked_mul(sign)?)?;
+            if m != 0 {
+                steps[n - m] += sign;
+            }
+            Some(())
+        };
+
+        // Where the rotations leave a dial that started at 0. Whole turns
+        // don't move it, so nothing here grows with the clicks.
+        let mut before = 0;
+        for &clicks in spins {
+            let turns: i128 = clicks.div_euclid(size).into();
+            let rest = clicks.rem_euclid(size).unsigned_abs() as usize;
+            let (wrapped, after) = wrap(n, before, rest);
+            // Started at s, the dial lands on 0 if s + after is a multiple of n
+            landed[(n - after) % n] += 1;
+
+            // The multiples of n in (s + before, s + before + clicks] turning
+            // right, and in [s + before + clicks, s + before) turning left
+            if clicks > zero {
+                add_floor(turns.checked_add(wrapped.into())?, after, 1)?;
+                add_floor(0, before, -1)?;
+            } else if clicks < zero {
+                let (k, m) = if before == 0 {
+                    (-1, n - 1)
+                } else {
+                    (0, before - 1)
+                };
+                let (wrapped, end) = wrap(n, m, rest);
+                add_floor(k, m, 1)?;
+                add_floor(k.checked_add(turns)?.checked_add(wrapped.into())?, end, -1)?;
+            }
+            before = after;
+        }
 
-    for signed_amount in spins {
-        let temp = dial + signed_amount;
-        let new_dial = temp.rem_euclid(DIAL_SIZE);
+        let mut passes = Vec::with_capacity(n);
+        let mut count = base;
+        for &step in &steps[..n] {
+            count = count.checked_add(step)?;
+            passes.push(usize::try_from(count).ok()?);
+        }
+
+        Some(StartCounts {
+            landed,
+            passes,
+            start: PhantomData,
+        })
+    }
 
-        count += (temp / DIAL_SIZE).abs();
-        if dial != 0 && temp <= 0 {
-            count += 1;
+    /// The count of `part` for every start, indexed by the start.
+    pub fn counts(&self, part: Part) -> &[usize] {
+        match part {
+            Part::One => &self.landed,
+            Part::Two => &self.passes,
         }
-        dial = new_dial
     }
 
-    count as usize
+    /// Start `s` as a position, which it fits since it's below the size.
+    fn start(s: usize) -> T {
+        T::from_magnitude(s as u128, false).expect("a start fits in the dial size's type")
+    }
+
+    /// Every start for which `part` counts exactly `count`.
+    pub fn starts(&self, part: Part, count: usize) -> impl Iterator<Item = T> + '_ {
+        self.counts(part)
+            .iter()
+            .enumerate()
+            .filter(move |&(_, &c)| c == count)
+            .map(|(s, _)| Self::start(s))
+    }
+
+    /// The lowest start giving the highest count of `part`, and that count.
+    pub fn max(&self, part: Part) -> (T, usize) {
+        let (s, count) = self
+            .counts(part)
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by_key(|&(_, count)| Reverse(count))
+            .expect("a dial has at least one position");
+        (Self::start(s), count)
+    }
+
+    /// The lowest start giving the lowest count of `part`, and that count.
+    pub fn min(&self, part: Part) -> (T, usize) {
+        let (s, count) = self
+            .counts(part)
+            .iter()
+            .copied()
+            .enumerate()
+            .min_by_key(|&(_, count)| count)
+            .expect("a dial has at least one position");
+        (Self::start(s), count)
+    }
+}
+
+/// One rotation of a [`Lock`], written `L68` for dial 0 and `2:R15` for dial 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation<T = i32> {
+    pub dial: usize,
+    pub clicks: T,
+}
+
+/// Dials side by side, where a dial wrapping round turns the next one by a
+/// click, like an odometer. Dial 0 turns fastest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lock<T = i32> {
+    dials: Vec<Dial<T>>,
+    /// Landings on 0 of each dial.
+    landed: Vec<usize>,
+    /// Clicks leaving each dial at 0.
+    passes: Vec<u128>,
+}
+
+impl<T: Clicks> Lock<T> {
+    pub fn new(dials: Vec<Dial<T>>) -> Self {
+        let n = dials.len();
+        Lock {
+            dials,
+            landed: vec![0; n],
+            passes: vec![0; n],
+        }
+    }
+
+    pub fn dials(&self) -> &[Dial<T>] {
+        &self.dials
+    }
+
+    pub fn landed(&self) -> &[usize] {
+        &self.landed
+    }
+
+    pub fn passes(&self) -> &[u128] {
+        &self.passes
+    }
+
+    /// Turns `dial` by `clicks`, carrying into the dials after it. Returns
+    /// the carry off the last dial.
+    ///
+    /// # Panics
+    ///
+    /// If there is no such dial, or a dial is left at 0 more often than a
+    /// `u128` holds.
+    pub fn rotate(&mut self, dial: usize, clicks: T) -> T {
+        assert!(dial < self.dials.len(), "no dial {dial}");
+
+        let zero = T::from(0);
+        let mut carry = clicks;
+        for k in dial..self.dials.len() {
+            let step = self.dials[k].rotate(carry);
+            self.landed[k] += usize::from(step.landed);
+            self.passes[k] = self.passes[k]
+                .checked_add(step.passes)
+                .expect("too many passes for a u128");
+
+            carry = step.carry;
+            if carry == zero {
+                break;
+            }
+        }
+
+        carry
+    }
+
+    /// Applies every rotation in turn, returning the total carry off the last
+    /// dial, or `None` if more than an `i128` holds.
+    pub fn apply(&mut self, rotations: &[Rotation<T>]) -> Option<i128> {
+        let mut total = Some(0i128);
+        for rotation in rotations {
+            let carry = self.rotate(rotation.dial, rotation.clicks);
+            total = total.and_then(|total| total.checked_add(carry.into()));
+        }
+
+        total
+    }
+}
+
+fn parse_input<T: Clicks>(input: &str, line: &str) -> Result<T, ParseError> {
+    let (b, num) = line
+        .split_at_checked(1)
+        .ok_or_else(|| ParseError::at(1, input, line, "expected a rotation"))?;
+    let left = match b {
+        "L" => true,
+        "R" => false,
+        _ => return Err(ParseError::at(1, input, line, "expected 'L' or 'R'")),
+    };
+
+    num.parse::<u128>()
+        .ok()
+        .and_then(|num| T::from_magnitude(num, left))
+        .ok_or_else(|| ParseError::at(1, input, num, "expected a number of clicks"))
+}
+
+/// Parses rotations into clicks of type `T`, which must be wide enough for
+/// the largest of them.
+pub fn try_generator<T: Clicks>(input: &str) -> Result<Vec<T>, ParseError> {
+    input.lines().map(|line| parse_input(input, line)).collect()
+}
+
+/// Parses rotations for a [`Lock`], each optionally prefixed by its dial.
+pub fn try_lock_generator<T: Clicks>(input: &str) -> Result<Vec<Rotation<T>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (dial, rotation) = match line.split_once(':') {
+                Some((dial, rotation)) => (
+                    dial.parse()
+                        .map_err(|_| ParseError::at(1, input, dial, "expected a dial number"))?,
+                    rotation,
+                ),
+                None => (0, line),
+            };
+
+            Ok(Rotation {
+                dial,
+                clicks: parse_input(input, rotation)?,
+            })
+        })
+        .collect()
+}
+
+#[aoc_generator(day1)]
+pub fn generator(input: &str) -> Vec<i64> {
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// How many rotations leave the puzzle's dial at 0.
+pub fn count_landed<T: Clicks>(spins: &[T]) -> usize {
+    Dial::default()
+        .simulate(spins.iter().copied())
+        .filter(|step| step.landed)
+        .count()
+}
+
+/// How many clicks leave the puzzle's dial at 0, or `None` if more than a
+/// `u128` holds.
+pub fn count_passes<T: Clicks>(spins: &[T]) -> Option<u128> {
+    Dial::default()
+        .simulate(spins.iter().copied())
+        .try_fold(0u128, |passes, step| passes.checked_add(step.passes))
+}
+
+#[aoc(day1, part1)]
+pub fn part1(spins: &[i64]) -> usize {
+    count_landed(spins)
+}
+
+#[aoc(day1, part2)]
+pub fn part2(spins: &[i64]) -> u128 {
+    // Each rotation passes 0 at most 2^63 / 100 + 1 times, so even a
+    // rotation for every byte of memory can't fill a u128
+    count_passes(spins).expect("too many passes for a u128")
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 1;
+    type Parsed = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(spins: &Self::Parsed) -> Answer {
+        part1(spins).into()
+    }
+
+    fn part2(spins: &Self::Parsed) -> Answer {
+        part2(spins).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Turns the dial one click at a time.
+
+    pub fn solve(input: &str) -> (usize, u128) {
+        let (mut dial, mut landed, mut passed) = (50, 0, 0);
+
+        for line in input.lines() {
+            let (direction, clicks) = line.split_at(1);
+            let step = if direction == "L" { 99 } else { 1 };
+
+            for _ in 0..clicks.parse::<u32>().unwrap() {
+                dial = (dial + step) % 100;
+                passed += u128::from(dial == 0);
+            }
+            landed += usize::from(dial == 0);
+        }
+
+        (landed, passed)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     const SAMPLE: &str = r"L68
@@ -74,6 +604,15 @@ L82";
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator::<i32>("L68\nX30\nR48").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = try_generator::<i32>("L68\nL3x0").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 3);
@@ -84,19 +623,277 @@ L82";
         assert_eq!(part2(&generator(SAMPLE)), 6);
     }
 
-    mod regression {
-        use super::*;
+    #[test]
+    pub fn dial_test() {
+        let steps = Dial::new(10, 3)
+            .simulate([-3, -25, 7, 30, 0])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            steps.iter().map(|s| s.position).collect::<Vec<_>>(),
+            [0, 5, 2, 2, 2]
+        );
+        assert_eq!(
+            steps
+                .iter()
+                .map(|s| (s.landed, s.passes))
+                .collect::<Vec<_>>(),
+            [(true, 1), (false, 2), (false, 1), (false, 3), (false, 0)]
+        );
 
-        const INPUT: &str = include_str!("../input/2025/day1.txt");
-        const ANSWERS: (usize, usize) = (1029, 5892);
+        let mut dial = Dial::default();
+        assert_eq!(dial.rotate(-50).passes, 1);
+        assert_eq!(dial.rotate(-100).passes, 1);
+        assert_eq!(dial.rotate(-1).carry, -1);
+        assert_eq!(dial.rotate(251).carry, 3);
+        assert_eq!((dial.size(), dial.position()), (100, 50));
+    }
+
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::solve(SAMPLE), (3, 6));
+
+        let synth = crate::synth::synth(1).unwrap();
+        for seed in 0..100 {
+            let input = synth.input(seed, 50);
+            let spins = generator(&input);
+            assert_eq!(
+                (part1(&spins), part2(&spins)),
+                oracle::solve(&input),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    pub fn index_test() {
+        let index = ZeroCrossingIndex::new(Dial::default(), &generator(SAMPLE)).unwrap();
+        assert_eq!(index.len(), 10);
+        assert_eq!((index.landed(0..10), index.passes(0..10)), (3, 6));
+        // L55 L1 L99 from 55: lands on 0 twice
+        assert_eq!(
+            (index.position(5), index.landed(5..8), index.passes(5..8)),
+            (55, 2, 2)
+        );
+        assert_eq!(index.landed(4..4), 0);
+
+        // Each passes 0 2^127 - 1 times
+        assert_eq!(
+            ZeroCrossingIndex::new(Dial::new(1, 0), &[i128::MAX; 3]),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a range")]
+    pub fn reversed_range_test() {
+        let index = ZeroCrossingIndex::new(Dial::default(), &generator(SAMPLE)).unwrap();
+        let (start, end) = (5, 4);
+        index.passes(start..end);
+    }
+
+    #[test]
+    pub fn wide_test() {
+        // 50 + i32::MAX would overflow an i32
+        let spins = generator("R2147483647\nL2147483647");
+        assert_eq!((part1(&spins), part2(&spins)), (0, 42949672));
+        assert!(try_generator::<i32>("R2147483648").is_err());
+        let answer = crate::solve(1, Part::Two, "R3000000000").unwrap();
+        assert_eq!(answer.to_string(), "30000000");
+
+        let spins = try_generator::<i64>("R9223372036854775807\nL9223372036854775807").unwrap();
+        assert_eq!(count_passes(&spins), Some(184467440737095516));
+        let spins = try_generator::<i128>("R170141183460469231731687303715884105727").unwrap();
+        assert_eq!(
+            count_passes(&spins),
+            Some(1701411834604692317316873037158841057)
+        );
+
+        let mut dial = Dial::new(1, 0);
+        assert_eq!(dial.rotate(i128::MIN).passes, 1 << 127);
+    }
+
+    #[test]
+    pub fn start_counts_test() {
+        let spins = generator(SAMPLE);
+        let starts = StartCounts::new(100, &spins).unwrap();
+        assert_eq!(starts.counts(Part::One)[50], 3);
+        assert_eq!(starts.counts(Part::Two)[50], 6);
+        assert!(starts.starts(Part::One, 3).any(|start| start == 50));
+
+        let simulate = |start| {
+            let steps = Dial::new(100, start).simulate(spins.iter().copied());
+            steps.fold((0, 0), |(landed, passes), step| {
+                (landed + usize::from(step.landed), passes + step.passes)
+            })
+        };
+        let (best, most) = starts.max(Part::Two);
+        assert_eq!(simulate(best).1, most as u128);
+        assert!((0..100).all(|start| simulate(start).1 <= most as u128));
+        let (worst, fewest) = starts.min(Part::One);
+        assert_eq!(simulate(worst).0, fewest);
+
+        let wide = spins.iter().map(|&c| i128::from(c)).collect::<Vec<_>>();
+        let wide = StartCounts::new(100, &wide).unwrap();
+        for part in Part::ALL {
+            assert_eq!(wide.counts(part), starts.counts(part));
+        }
+        assert_eq!(StartCounts::new(1, &[i128::MAX; 3]), None);
+    }
+
+    #[test]
+    pub fn lock_test() {
+        let rotations = try_lock_generator("R25\n0:L6\n1:R8\n2:L1").unwrap();
+        assert_eq!(
+            rotations[1],
+            Rotation {
+                dial: 0,
+                clicks: -6
+            }
+        );
+
+        let mut lock = Lock::new(vec![Dial::new(10, 0), Dial::new(10, 0), Dial::new(5, 0)]);
+        assert_eq!(lock.apply(&rotations[..2]), Some(0));
+        assert_eq!(
+            lock.dials().iter().map(Dial::position).collect::<Vec<_>>(),
+            [9, 1, 0]
+        );
+        // 1 + 8 wraps dial 1 to 9 without carrying, then dial 2 borrows off the end
+        assert_eq!(lock.apply(&rotations[2..]), Some(-1));
+        assert_eq!(
+            lock.dials().iter().map(Dial::position).collect::<Vec<_>>(),
+            [9, 9, 4]
+        );
+        assert_eq!(lock.passes(), [3, 0, 0]);
+        assert_eq!(lock.landed(), [0, 0, 0]);
+
+        // Carries add up in an i128, and only overflow that
+        let rotation = Rotation {
+            dial: 0,
+            clicks: i32::MAX,
+        };
+        let mut lock = Lock::new(vec![Dial::new(1, 0)]);
+        assert_eq!(lock.apply(&[rotation; 2]), Some(2 * i128::from(i32::MAX)));
+        let rotation = Rotation {
+            dial: 0,
+            clicks: i128::MAX,
+        };
+        let mut lock = Lock::new(vec![Dial::new(1, 0)]);
+        assert_eq!(lock.apply(&[rotation; 2]), None);
+
+        let err = try_lock_generator::<i32>("R5\nx:L5").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = try_lock_generator::<i32>("R5\n1:X5").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+
+    proptest! {
+        #[test]
+        fn passes_at_least_lands_test(seed in any::<u64>(), size in 1..200usize) {
+            // Every rotation that lands on 0 also passes it on the last click
+            let spins = generator(&crate::synth::synth(1).unwrap().input(seed, size));
+            prop_assert!(part2(&spins) >= part1(&spins) as u128);
+        }
+
+        #[test]
+        fn wide_matches_i128_test(spins in prop::collection::vec(-i64::MAX..=i64::MAX, 0..20)) {
+            // The original i32 arithmetic, in an i128 that i64 clicks can't overflow
+            let (mut dial, mut landed, mut passes) = (50i128, 0, 0);
+            for &clicks in &spins {
+                let turned = dial + i128::from(clicks);
+                passes += (turned / 100).unsigned_abs();
+                if dial != 0 && turned <= 0 {
+                    passes += 1;
+                }
+                dial = turned.rem_euclid(100);
+                landed += usize::from(dial == 0);
+            }
+
+            prop_assert_eq!(count_landed(&spins), landed);
+            prop_assert_eq!(count_passes(&spins), Some(passes));
+        }
+
+        #[test]
+        fn index_matches_dial_test(seed in any::<u64>(), a in 0..=100usize, b in 0..=100usize) {
+            let spins = generator(&crate::synth::synth(1).unwrap().input(seed, 100));
+            let index = ZeroCrossingIndex::new(Dial::new(100, 50), &spins).unwrap();
+            let range = a.min(b)..a.max(b);
+
+            // The same as turning a dial from where the index says it was
+            let dial = Dial::new(100, index.position(range.start));
+            let steps = dial
+                .simulate(spins[range.clone()].iter().copied())
+                .collect::<Vec<_>>();
+            let last = steps.last().map_or(dial.position(), |s| s.position);
+
+            let landed = steps.iter().filter(|s| s.landed).count();
+            let passes = steps.iter().map(|s| s.passes).sum::<u128>();
+
+            prop_assert_eq!(index.landed(range.clone()), landed);
+            prop_assert_eq!(index.passes(range.clone()), passes);
+            prop_assert_eq!(index.position(range.end), last);
+        }
+
+        #[test]
+        fn start_counts_simulate_test(
+            size in 1..40i32,
+            spins in prop::collection::vec(-300..300i32, 0..40),
+        ) {
+            let starts = StartCounts::new(size, &spins).unwrap();
+            for start in 0..size {
+                let dial = Dial::new(size, start);
+                let steps = dial.simulate(spins.iter().copied()).collect::<Vec<_>>();
+                let landed = steps.iter().filter(|s| s.landed).count();
+                let passes = steps.iter().map(|s| s.passes).sum::<u128>();
+                prop_assert_eq!(starts.counts(Part::One)[start as usize], landed);
+                prop_assert_eq!(starts.counts(Part::Two)[start as usize] as u128, passes);
+            }
+        }
+
+        #[test]
+        fn start_counts_wide_test(
+            size in 1..40i64,
+            spins in prop::collection::vec(any::<i32>(), 0..40),
+        ) {
+            let spins = spins.into_iter().map(i64::from).collect::<Vec<_>>();
+            let starts = StartCounts::new(size, &spins).unwrap();
+            for start in 0..size {
+                let steps = Dial::new(size, start).simulate(spins.iter().copied());
+                let passes = steps.map(|s| s.passes).sum::<u128>();
+                prop_assert_eq!(starts.counts(Part::Two)[start as usize] as u128, passes);
+            }
+        }
 
         #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
+        fn lock_counts_test(
+            sizes in prop::collection::vec(1..20i32, 1..5),
+            rotations in prop::collection::vec((0..5usize, -500..500i32), 0..50),
+        ) {
+            let mut lock = Lock::new(sizes.iter().map(|&size| Dial::new(size, 0)).collect());
+            let weights = sizes
+                .iter()
+                .scan(1i64, |weight, &size| {
+                    let w = *weight;
+                    *weight *= i64::from(size);
+                    Some(w)
+                })
+                .collect::<Vec<_>>();
+            let total = sizes.iter().map(|&size| i64::from(size)).product::<i64>();
 
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+            // An odometer adds exactly: what it reads, plus what carried off the end
+            let mut expected = 0;
+            let mut carried = 0;
+            for &(dial, clicks) in &rotations {
+                let dial = dial % sizes.len();
+                expected += weights[dial] * i64::from(clicks);
+                carried += i64::from(lock.rotate(dial, clicks));
+            }
+            let reading = lock
+                .dials()
+                .iter()
+                .zip(&weights)
+                .map(|(dial, weight)| i64::from(dial.position()) * weight)
+                .sum::<i64>();
+            prop_assert_eq!(reading + carried * total, expected);
         }
     }
 }
@@ -1,5 +1,11 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Solution},
+};
+
 struct Range {
     start: u64,
     end: u64,
@@ -48,20 +54,33 @@ const SECOND: &[Range] = &[
 /// Subtracted in part 2: `FIRST + SECOND - THIRD`.
 const THIRD: &[Range] = &[range(6, 1), range(10, 1)];
 
+/// An inclusive range of product IDs. These are kept as a plain list rather
+/// than an `IntervalSet`: an ID in two overlapping ranges counts once per range.
 type Pair = [u64; 2];
 
-#[aoc_generator(day2)]
-pub fn generator(input: &str) -> Vec<Pair> {
+fn parse_id(input: &str, id: &str) -> Result<u64, ParseError> {
+    id.parse()
+        .map_err(|_| ParseError::at(2, input, id, "expected a product ID"))
+}
+
+pub fn try_generator(input: &str) -> Result<Vec<Pair>, ParseError> {
     input
         .split(',')
         .map(|group| {
-            let (l, r) = group.split_once('-').unwrap();
+            let (l, r) = group
+                .split_once('-')
+                .ok_or_else(|| ParseError::at(2, input, group, "expected a range like 11-22"))?;
 
-            [l.parse().unwrap(), r.parse().unwrap()]
+            Ok([parse_id(input, l)?, parse_id(input, r)?])
         })
         .collect()
 }
 
+#[aoc_generator(day2)]
+pub fn generator(input: &str) -> Vec<Pair> {
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
+}
+
 #[aoc(day2, part1)]
 pub fn part1(input: &[Pair]) -> u64 {
     sum(FIRST, input)
@@ -91,9 +110,61 @@ fn sum(ranges: &[Range], input: &[Pair]) -> u64 {
     result
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 2;
+    type Parsed = Vec<Pair>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(input: &Self::Parsed) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Parsed) -> Answer {
+        part2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Checks every ID in every range.
+
+    /// Whether `id` is a sequence of digits repeated `times` times.
+    fn repeats(id: u64, times: usize) -> bool {
+        let id = id.to_string();
+        id.len().is_multiple_of(times) && id == id[..id.len() / times].repeat(times)
+    }
+
+    pub fn solve(input: &str) -> (u64, u64) {
+        let (mut part1, mut part2) = (0, 0);
+
+        for range in input.split(',') {
+            let (from, to) = range.split_once('-').unwrap();
+            for id in from.parse::<u64>().unwrap()..=to.parse().unwrap() {
+                if repeats(id, 2) {
+                    part1 += id;
+                }
+                if (2..=id.to_string().len()).any(|times| repeats(id, times)) {
+                    part2 += id;
+                }
+            }
+        }
+
+        (part1, part2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::rng::Rng;
 
     const SAMPLE: &str = r"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
 
@@ -104,9 +175,20 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator("11-22,95115").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 7));
+
+        let err = try_generator("11-22,95-1x5").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 10));
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 1227775554);
+        // Ranges are summed separately, even where they overlap
+        assert_eq!(part1(&generator("11-22,20-33")), 11 + 22 + 22 + 33);
     }
 
     #[test]
@@ -114,19 +196,42 @@ mod tests {
         assert_eq!(part2(&generator(SAMPLE)), 4174379265);
     }
 
-    mod regression {
-        use super::*;
-
-        const INPUT: &str = include_str!("../input/2025/day2.txt");
-        const ANSWERS: (u64, u64) = (21139440284, 38731915928);
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::solve(SAMPLE), (1227775554, 4174379265));
+
+        for seed in 0..50 {
+            // Short ranges, mostly around IDs made of a repeated pattern
+            let mut rng = Rng::new(seed);
+            let input = (0..8)
+                .map(|_| {
+                    let digits = rng.range(1..=10) as usize;
+                    let size = (1..=digits).filter(|size| digits.is_multiple_of(*size));
+                    let size = *rng.pick(&size.collect::<Vec<_>>());
+                    let pattern =
+                        rng.range(10u64.pow(size as u32 - 1)..=10u64.pow(size as u32) - 1);
+                    let id: u64 = pattern.to_string().repeat(digits / size).parse().unwrap();
+
+                    let from = id.saturating_sub(rng.range(0..=300)).max(1);
+                    format!("{from}-{}", (id + rng.range(0..=300)).min(9_999_999_999))
+                })
+                .join(",");
+
+            let ranges = generator(&input);
+            assert_eq!(
+                (part1(&ranges), part2(&ranges)),
+                oracle::solve(&input),
+                "{input}"
+            );
+        }
+    }
 
+    proptest! {
         #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
-
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+        fn part2_at_least_part1_test(seed in any::<u64>(), size in 1..40usize) {
+            // An ID repeated twice is repeated at least twice
+            let ids = generator(&crate::synth::synth(2).unwrap().input(seed, size));
+            prop_assert!(part2(&ids) >= part1(&ids));
         }
     }
 }
@@ -1,8 +1,44 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Solution},
+    trace,
+};
+
+/// Part 2 turns on this many batteries in every bank.
+const MOST_BATTERIES: usize = 12;
+
+pub fn try_generator(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            if let Some(pos) = line.bytes().position(|b| !b.is_ascii_digit()) {
+                return Err(ParseError::at(
+                    3,
+                    input,
+                    &line[pos..],
+                    "expected a joltage digit",
+                ));
+            }
+            if line.len() < MOST_BATTERIES {
+                return Err(ParseError::at(
+                    3,
+                    input,
+                    &line[line.len()..],
+                    format!("expected at least {MOST_BATTERIES} batteries"),
+                ));
+            }
+
+            Ok(line.as_bytes().to_vec())
+        })
+        .collect()
+}
+
 #[aoc_generator(day3)]
 pub fn generator(input: &str) -> Vec<Vec<u8>> {
-    input.lines().map(|line| line.as_bytes().to_vec()).collect()
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 fn find_left_most_max(slice: &[u8]) -> (usize, u8) {
@@ -44,6 +80,7 @@ fn solve<const N: usize>(lines: &[Vec<u8>]) -> u64 {
             skips_remaining -= pos;
             idx += pos + 1;
             joltage = joltage * 10 + u64::from(next_digit - b'0');
+            trace!("pick", position = idx - 1, digit = next_digit - b'0');
 
             // If we have no more skips left, we can take the rest of the digits directly.
             // This is because line[idx..idx+1+0] is just a single digit at this point.
@@ -57,6 +94,7 @@ fn solve<const N: usize>(lines: &[Vec<u8>]) -> u64 {
             }
         }
 
+        trace!("bank", joltage = joltage);
         total += joltage;
     }
 
@@ -70,12 +108,58 @@ pub fn part1(lines: &[Vec<u8>]) -> u64 {
 
 #[aoc(day3, part2)]
 pub fn part2(lines: &[Vec<u8>]) -> u64 {
-    solve::<12>(lines)
+    solve::<MOST_BATTERIES>(lines)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 3;
+    type Parsed = Vec<Vec<u8>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(lines: &Self::Parsed) -> Answer {
+        part1(lines).into()
+    }
+
+    fn part2(lines: &Self::Parsed) -> Answer {
+        part2(lines).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Tries every choice of batteries in every bank.
+
+    pub fn solve(input: &str, batteries: usize) -> u64 {
+        input
+            .lines()
+            .map(|bank| {
+                let bank = bank.as_bytes();
+                (0u32..1 << bank.len())
+                    .filter(|mask| mask.count_ones() as usize == batteries)
+                    .map(|mask| {
+                        (0..bank.len())
+                            .filter(|i| mask & 1 << i != 0)
+                            .fold(0, |joltage, i| joltage * 10 + u64::from(bank[i] - b'0'))
+                    })
+                    .max()
+                    .unwrap()
+            })
+            .sum()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::rng::Rng;
 
     const SAMPLE: &str = r"987654321111111
 811111111111119
@@ -89,6 +173,16 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator("987654321111111\n81111111 111119").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 9));
+
+        let err = try_generator("987654321111111\n12").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        assert!(crate::solve(3, crate::solution::Part::Two, "12").is_err());
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 357);
@@ -99,19 +193,47 @@ mod tests {
         assert_eq!(part2(&generator(SAMPLE)), 3121910778619);
     }
 
-    mod regression {
-        use super::*;
-
-        const INPUT: &str = include_str!("../input/2025/day3.txt");
-        const ANSWERS: (u64, u64) = (17613, 175304218462560);
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::solve(SAMPLE, 2), 357);
+        assert_eq!(oracle::solve(SAMPLE, 12), 3121910778619);
+
+        for seed in 0..30 {
+            // Banks short enough to enumerate, with few distinct digits so
+            // there are plenty of ties
+            let mut rng = Rng::new(seed);
+            let input = (0..5)
+                .map(|_| {
+                    let len = rng.range(12..=15);
+                    let digits = rng.range(1..=9);
+                    (0..len)
+                        .map(|_| char::from(b'0' + rng.range(1..=digits) as u8))
+                        .collect::<String>()
+                })
+                .join("\n");
+
+            let banks = generator(&input);
+            assert_eq!(part1(&banks), oracle::solve(&input, 2), "{input}");
+            assert_eq!(part2(&banks), oracle::solve(&input, 12), "{input}");
+        }
+    }
 
+    proptest! {
         #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
-
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+        fn subsequence_test(bank in "[1-9]{12,40}") {
+            let lines = [bank.clone().into_bytes()];
+
+            for (batteries, joltage) in [(2, solve::<2>(&lines)), (12, solve::<12>(&lines))] {
+                let joltage = joltage.to_string();
+                let mut rest = bank.chars();
+                prop_assert_eq!(joltage.len(), batteries);
+                prop_assert!(
+                    joltage.chars().all(|digit| rest.any(|b| b == digit)),
+                    "{} is not a subsequence of {}",
+                    joltage,
+                    bank
+                );
+            }
         }
     }
 }
@@ -1,76 +1,129 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::common::utils::neighbors_diag;
+use crate::{
+    error::ParseError,
+    grid::Grid,
+    inputs::normalize,
+    solution::{Answer, Solution},
+    trace,
+};
+
+pub fn try_generator(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(4, input, "expected '@' or '.'", |b| {
+        matches!(b, b'@' | b'.').then_some(b)
+    })
+}
 
 #[aoc_generator(day4)]
-pub fn generator(input: &str) -> (Vec<u8>, usize, usize) {
-    let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
-
-    let mut v = input.as_bytes().to_vec();
-    v.push(b'\n');
-    (v, height, width)
+pub fn generator(input: &str) -> Grid<u8> {
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
 }
 
-fn neighbor_check(inputs: &[u8], r: usize, c: usize, r_max: usize, c_max: usize) -> bool {
-    neighbors_diag(r, c, r_max, c_max)
-        .filter(|&(nr, nc)| inputs[nr * (c_max + 1) + nc] == b'@')
-        .count()
-        < 4
+/// Whether there's a roll at `pos` that a forklift can reach.
+fn accessible(grid: &Grid<u8>, pos: (usize, usize)) -> bool {
+    grid[pos] == b'@' && grid.neighbors8(pos).filter(|&n| grid[n] == b'@').count() < 4
 }
 
 #[aoc(day4, part1)]
-pub fn part1((inputs, r_max, c_max): &(Vec<u8>, usize, usize)) -> usize {
-    let mut sum = 0;
-    for r in 0..*r_max {
-        for c in 0..*c_max {
-            let idx = r * (c_max + 1) + c;
-            if inputs[idx] != b'@' {
-                continue;
-            }
-
-            if neighbor_check(inputs, r, c, *r_max, *c_max) {
-                sum += 1;
-            }
-        }
-    }
-    sum
+pub fn part1(grid: &Grid<u8>) -> usize {
+    grid.positions()
+        .filter(|&pos| accessible(grid, pos))
+        .count()
 }
 
 #[aoc(day4, part2)]
-pub fn part2((inputs, r_max, c_max): &(Vec<u8>, usize, usize)) -> usize {
+pub fn part2(grid: &Grid<u8>) -> usize {
     let mut count = 0;
-    let mut inputs = inputs.to_vec();
+    let mut grid = grid.clone();
     let mut to_remove = Vec::with_capacity(2048);
 
     loop {
-        for r in 0..*r_max {
-            for c in 0..*c_max {
-                let idx = r * (c_max + 1) + c;
-                if inputs[idx] != b'@' {
-                    continue;
-                }
-
-                if neighbor_check(&inputs, r, c, *r_max, *c_max) {
-                    to_remove.push(idx);
-                }
-            }
-        }
+        to_remove.extend(grid.positions().filter(|&pos| accessible(&grid, pos)));
 
         count += to_remove.len();
+        trace!("wave", removed = to_remove.len(), total = count);
         if to_remove.is_empty() {
             break count;
         }
 
-        for &idx in &to_remove {
-            inputs[idx] = b'.';
+        for &pos in &to_remove {
+            grid[pos] = b'.';
         }
         to_remove.clear();
     }
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 4;
+    type Parsed = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Counts neighbours the long way round, and removes one roll at a time.
+
+    fn accessible(grid: &[Vec<bool>], r: usize, c: usize) -> bool {
+        let mut neighbours = 0;
+        for dr in -1..=1 {
+            for dc in -1..=1 {
+                let (nr, nc) = (r as isize + dr, c as isize + dc);
+                if (dr, dc) != (0, 0)
+                    && (0..grid.len() as isize).contains(&nr)
+                    && (0..grid[0].len() as isize).contains(&nc)
+                    && grid[nr as usize][nc as usize]
+                {
+                    neighbours += 1;
+                }
+            }
+        }
+        grid[r][c] && neighbours < 4
+    }
+
+    fn find(grid: &[Vec<bool>]) -> Option<(usize, usize)> {
+        (0..grid.len())
+            .flat_map(|r| (0..grid[0].len()).map(move |c| (r, c)))
+            .find(|&(r, c)| accessible(grid, r, c))
+    }
+
+    pub fn solve(input: &str) -> (usize, usize) {
+        let mut grid = input
+            .lines()
+            .map(|line| line.bytes().map(|b| b == b'@').collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let part1 = (0..grid.len())
+            .flat_map(|r| (0..grid[0].len()).map(move |c| (r, c)))
+            .filter(|&(r, c)| accessible(&grid, r, c))
+            .count();
+
+        let mut part2 = 0;
+        while let Some((r, c)) = find(&grid) {
+            grid[r][c] = false;
+            part2 += 1;
+        }
+
+        (part1, part2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     const SAMPLE: &str = r"..@@.@@@@.
@@ -92,6 +145,15 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator("..@@\n@@@\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = try_generator("..@@\n@#@.\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 13);
@@ -102,19 +164,31 @@ mod tests {
         assert_eq!(part2(&generator(SAMPLE)), 43);
     }
 
-    mod regression {
-        use super::*;
-
-        const INPUT: &str = include_str!("../input/2025/day4.txt");
-        const ANSWERS: (usize, usize) = (1435, 8623);
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::solve(SAMPLE), (13, 43));
+
+        let synth = crate::synth::synth(4).unwrap();
+        for seed in 0..50 {
+            let input = synth.input(seed, 12);
+            let grid = generator(&input);
+            assert_eq!(
+                (part1(&grid), part2(&grid)),
+                oracle::solve(&input),
+                "{input}"
+            );
+        }
+    }
 
+    proptest! {
         #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
-
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+        fn part2_at_least_part1_test(seed in any::<u64>(), size in 1..40usize) {
+            // The first wave removes every roll part 1 counts, and no more
+            // rolls can go than there are
+            let input = crate::synth::synth(4).unwrap().input(seed, size);
+            let grid = generator(&input);
+            prop_assert!(part2(&grid) >= part1(&grid));
+            prop_assert!(part2(&grid) <= input.matches('@').count());
         }
     }
 }
@@ -1,56 +1,68 @@
+use std::ops::RangeInclusive;
+
 use aoc_runner_derive::{aoc, aoc_generator};
 use nom::{IResult, Parser, bytes::complete::tag, combinator::all_consuming};
 
-use crate::common::nom::{nom_lines, nom_u64};
+use crate::{
+    common::nom::nom_u64,
+    error::ParseError,
+    inputs::normalize,
+    interval_set::IntervalSet,
+    solution::{Answer, Solution},
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct IngredientInfo {
-    ranges: Vec<Range>,
+    fresh: IntervalSet<u64>,
     ids: Vec<u64>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-pub struct Range {
-    start: u64,
-    end: u64,
-}
-
-impl Range {
-    fn merge_overlapping(&self, b: Self) -> Option<Self> {
-        if self.end < b.start || b.end < self.start {
-            return None;
-        }
-
-        Some(Self {
-            start: self.start.min(b.start),
-            end: self.end.max(b.end),
-        })
-    }
-}
-
-fn parse_range(s: &str) -> IResult<&str, Range> {
+fn parse_range(s: &str) -> IResult<&str, RangeInclusive<u64>> {
     let (s, start) = nom_u64(s)?;
     let (s, _) = tag("-").parse(s)?;
     let (s, end) = nom_u64(s)?;
-    Ok((s, Range { start, end }))
+    Ok((s, start..=end))
 }
 
-fn parse_input(s: &str) -> IResult<&str, (Vec<Range>, Vec<u64>)> {
-    let (s, ranges) = nom_lines(parse_range).parse(s)?;
-    let (s, _) = tag("\n\n").parse(s)?;
-    let (s, ids) = nom_lines(nom_u64).parse(s)?;
+/// Parses each line of `section`, a subslice of `input`, on its own, so errors
+/// point into the line that is wrong.
+fn parse_lines<'a, O>(
+    input: &str,
+    section: &'a str,
+    parser: fn(&'a str) -> IResult<&'a str, O>,
+) -> Result<Vec<O>, ParseError> {
+    section
+        .lines()
+        .map(|line| {
+            all_consuming(parser)
+                .parse(line)
+                .map(|(_, o)| o)
+                .map_err(|e| ParseError::from_nom(5, input, e))
+        })
+        .collect()
+}
 
-    Ok((s, (ranges, ids)))
+pub fn try_generator(input: &str) -> Result<IngredientInfo, ParseError> {
+    let (ranges, ids) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::new(
+            5,
+            input,
+            input.len(),
+            "expected a blank line before the IDs",
+        )
+    })?;
+
+    Ok(IngredientInfo {
+        fresh: parse_lines(input, ranges, parse_range)?
+            .into_iter()
+            .collect(),
+        ids: parse_lines(input, ids, nom_u64)?,
+    })
 }
 
 #[aoc_generator(day5)]
 pub fn generator(input: &str) -> IngredientInfo {
-    let (ranges, ids) = all_consuming(parse_input).parse(input).unwrap().1;
-
-    IngredientInfo {
-        ranges: merge_ranges(ranges),
-        ids,
-    }
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 #[aoc(day5, part1)]
@@ -58,52 +70,64 @@ pub fn part1(inputs: &IngredientInfo) -> usize {
     inputs
         .ids
         .iter()
-        .filter(|&id| {
-            inputs
-                .ranges
-                .iter()
-                .any(|Range { start, end }| id >= start && id <= end)
-        })
+        .filter(|&&id| inputs.fresh.contains(id))
         .count()
 }
 
-fn merge_ranges(mut ranges: Vec<Range>) -> Vec<Range> {
-    // We require sorted ranges to make merging easier
-    ranges.sort_unstable_by_key(|r| r.start);
-
-    let mut disjoint_ranges = Vec::with_capacity(ranges.len());
-    disjoint_ranges.push(ranges[0]);
-
-    for current in ranges[1..].iter().copied() {
-        eprintln!("Current range: {:?}", current);
-        eprintln!("Disjoint ranges: {:?}", disjoint_ranges);
-        // We only need to check the last range in disjoint_ranges for overlap
-        let last = disjoint_ranges.last_mut().unwrap();
-
-        if let Some(merged_range) = last.merge_overlapping(current) {
-            *last = merged_range;
-        } else {
-            // No overlap, simply add the current range to the end of disjoint_ranges
-            // next time through, we will be comparing against this one
-            disjoint_ranges.push(current);
-        }
+#[aoc(day5, part2)]
+pub fn part2(inputs: &IngredientInfo) -> u128 {
+    inputs.fresh.len()
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 5;
+    type Parsed = IngredientInfo;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(inputs: &Self::Parsed) -> Answer {
+        part1(inputs).into()
     }
 
-    disjoint_ranges
+    fn part2(inputs: &Self::Parsed) -> Answer {
+        part2(inputs).into()
+    }
 }
 
-#[aoc(day5, part2)]
-pub fn part2(inputs: &IngredientInfo) -> u64 {
-    inputs
-        .ranges
-        .iter()
-        .map(|range| range.end - range.start + 1)
-        .sum()
+#[cfg(test)]
+mod oracle {
+    //! Checks every ID against every range, for small IDs.
+
+    pub fn solve(input: &str) -> (usize, usize) {
+        let (ranges, ids) = input.split_once("\n\n").unwrap();
+        let ranges = ranges
+            .lines()
+            .map(|line| {
+                let (start, end) = line.split_once('-').unwrap();
+                start.parse::<u64>().unwrap()..=end.parse().unwrap()
+            })
+            .collect::<Vec<_>>();
+        let fresh = |id| ranges.iter().any(|range| range.contains(&id));
+
+        let part1 = ids.lines().filter(|id| fresh(id.parse().unwrap())).count();
+        let part2 = (0..=ranges.iter().map(|range| *range.end()).max().unwrap())
+            .filter(|&id| fresh(id))
+            .count();
+
+        (part1, part2)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+
     use super::*;
+    use crate::rng::Rng;
 
     const SAMPLE: &str = r"3-5
 10-14
@@ -124,6 +148,15 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator("3-5\n10-14\n16+20\n\n1").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+
+        let err = try_generator("3-5\n\n1\nx").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 1));
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 3);
@@ -132,21 +165,31 @@ mod tests {
     #[test]
     pub fn part2_test() {
         assert_eq!(part2(&generator(SAMPLE)), 14);
+        assert_eq!(part2(&generator("0-18446744073709551615\n\n1")), 1 << 64);
     }
 
-    mod regression {
-        use super::*;
-
-        const INPUT: &str = include_str!("../input/2025/day5.txt");
-        const ANSWERS: (usize, u64) = (509, 336790092076620);
-
-        #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
-
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::solve(SAMPLE), (3, 14));
+
+        for seed in 0..100 {
+            // Small values, so ranges nest, overlap and touch
+            let mut rng = Rng::new(seed);
+            let ranges = (0..rng.range(1..=10))
+                .map(|_| {
+                    let start = rng.range(0..=200);
+                    format!("{start}-{}", start + rng.range(0..=30))
+                })
+                .join("\n");
+            let ids = (0..20).map(|_| rng.range(0..=240)).join("\n");
+            let input = format!("{ranges}\n\n{ids}");
+
+            let info = generator(&input);
+            assert_eq!(
+                (part1(&info), part2(&info) as usize),
+                oracle::solve(&input),
+                "{input}"
+            );
         }
     }
 }
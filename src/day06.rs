@@ -1,17 +1,29 @@
-use std::ops::Range;
+use std::{iter, ops::Range};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 use arrayvec::ArrayVec;
 
-fn parse_op_with_ranges(chunk: &str) -> (Vec<u8>, Vec<Range<usize>>) {
+use crate::{
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Solution},
+};
+
+/// Number cells grouped by column, and each column's operator.
+type Worksheet = (Vec<ArrayVec<[u8; 4], 4>>, Vec<u8>);
+
+/// Splits the operator line into columns, padding it with spaces to `width`,
+/// which must be at least its length.
+fn parse_op_with_ranges(chunk: &str, width: usize) -> (Vec<u8>, Vec<Range<usize>>) {
     let mut ops = Vec::new();
     let mut ranges = Vec::new();
 
     let bytes = chunk.as_bytes();
     let mut start = 0;
     let mut in_op = false;
+    let padded = bytes.iter().copied().chain(iter::repeat(b' '));
 
-    for (i, &b) in bytes.iter().enumerate() {
+    for (i, b) in padded.enumerate().take(width + 1) {
         if b != b' ' && !in_op {
             in_op = true;
             if i != 0 {
@@ -24,16 +36,52 @@ fn parse_op_with_ranges(chunk: &str) -> (Vec<u8>, Vec<Range<usize>>) {
         }
     }
 
-    ranges.push(start..bytes.len());
+    ranges.push(start..width);
 
-    debug_assert_eq!(ops.len(), ranges.len());
     (ops, ranges)
 }
 
-#[aoc_generator(day6)]
-pub fn generator(input: &str) -> (Vec<ArrayVec<[u8; 4], 4>>, Vec<u8>) {
+pub fn try_generator(input: &str) -> Result<Worksheet, ParseError> {
     let mut iter = input.lines().rev();
-    let (ops, ranges) = parse_op_with_ranges(iter.next().unwrap());
+    let op_line = iter
+        .next()
+        .ok_or_else(|| ParseError::new(6, input, 0, "expected a line of operators"))?;
+
+    if let Some(pos) = op_line
+        .bytes()
+        .position(|b| !matches!(b, b'+' | b'*' | b' '))
+    {
+        return Err(ParseError::at(
+            6,
+            input,
+            &op_line[pos..],
+            "expected '+' or '*'",
+        ));
+    }
+    if let Some(pos) = op_line
+        .as_bytes()
+        .windows(2)
+        .position(|pair| pair.iter().all(|&b| b != b' '))
+    {
+        return Err(ParseError::at(
+            6,
+            input,
+            &op_line[pos + 1..],
+            "expected every operator to be followed by padding",
+        ));
+    }
+
+    // normalize strips trailing padding, so pad the operators to the widest line
+    let width = input.lines().map(str::len).max().unwrap_or(0);
+    let (ops, ranges) = parse_op_with_ranges(op_line, width);
+    if ops.len() != ranges.len() {
+        return Err(ParseError::at(
+            6,
+            input,
+            op_line,
+            "expected an operator at the start of every column",
+        ));
+    }
 
     let columns = ops.len();
     let mut nums = vec![ArrayVec::new(); columns];
@@ -42,22 +90,42 @@ pub fn generator(input: &str) -> (Vec<ArrayVec<[u8; 4], 4>>, Vec<u8>) {
         let bytes = line.as_bytes();
 
         for (col, range) in ranges.iter().enumerate() {
-            let slice = &bytes[range.clone()];
-
-            nums[col].push([
-                slice.first().copied().unwrap_or(b' '),
-                slice.get(1).copied().unwrap_or(b' '),
-                slice.get(2).copied().unwrap_or(b' '),
-                slice.get(3).copied().unwrap_or(b' '),
-            ]);
+            // Likewise treat short lines as blank-padded
+            let slice = &bytes[range.start.min(bytes.len())..range.end.min(bytes.len())];
+            let cell = line.get(range.start..).unwrap_or(line);
+
+            if slice.len() > 4 {
+                return Err(ParseError::at(6, input, cell, "expected at most 4 digits"));
+            }
+            if !slice.iter().any(u8::is_ascii_digit)
+                || !slice.iter().all(|&b| b.is_ascii_digit() || b == b' ')
+            {
+                return Err(ParseError::at(6, input, cell, "expected a number"));
+            }
+
+            nums[col]
+                .try_push([
+                    slice.first().copied().unwrap_or(b' '),
+                    slice.get(1).copied().unwrap_or(b' '),
+                    slice.get(2).copied().unwrap_or(b' '),
+                    slice.get(3).copied().unwrap_or(b' '),
+                ])
+                .map_err(|_| {
+                    ParseError::at(6, input, line, "expected at most 4 rows of numbers")
+                })?;
         }
     }
 
-    (nums, ops)
+    Ok((nums, ops))
+}
+
+#[aoc_generator(day6)]
+pub fn generator(input: &str) -> Worksheet {
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 #[aoc(day6, part1)]
-pub fn part1((nums, ops): &(Vec<ArrayVec<[u8; 4], 4>>, Vec<u8>)) -> u64 {
+pub fn part1((nums, ops): &Worksheet) -> u64 {
     let mut total = 0;
 
     for (col_nums, op) in nums.iter().zip(ops.iter()) {
@@ -96,7 +164,7 @@ fn rotate_numbers(nums: &[[u8; 4]]) -> ArrayVec<u64, 4> {
 }
 
 #[aoc(day6, part2)]
-pub fn part2((nums, ops): &(Vec<ArrayVec<[u8; 4], 4>>, Vec<u8>)) -> u64 {
+pub fn part2((nums, ops): &Worksheet) -> u64 {
     let mut total = 0;
 
     for (num_col, op) in nums.iter().zip(ops.iter()) {
@@ -113,8 +181,87 @@ pub fn part2((nums, ops): &(Vec<ArrayVec<[u8; 4], 4>>, Vec<u8>)) -> u64 {
     total
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 6;
+    type Parsed = Worksheet;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Splits the worksheet on blank columns and reads each problem by rows,
+    //! then by columns.
+
+    fn total(op: char, numbers: impl Iterator<Item = u64>) -> u64 {
+        if op == '*' {
+            numbers.product()
+        } else {
+            numbers.sum()
+        }
+    }
+
+    pub fn solve(input: &str) -> (u64, u64) {
+        let lines = input.lines().collect::<Vec<_>>();
+        let width = lines.iter().map(|line| line.len()).max().unwrap();
+        let grid = lines
+            .iter()
+            .map(|line| format!("{line:<width$}").into_bytes())
+            .collect::<Vec<_>>();
+        let (numbers, ops) = grid.split_at(grid.len() - 1);
+
+        let blank = |col: usize| grid.iter().all(|row| row[col] == b' ');
+        let (mut part1, mut part2) = (0, 0);
+        let mut start = 0;
+
+        for end in (0..=width).filter(|&col| col == width || blank(col)) {
+            if start < end {
+                let op = ops[0][start..end]
+                    .iter()
+                    .map(|&b| char::from(b))
+                    .find(|&c| c != ' ')
+                    .unwrap();
+                let read = |digits: &mut dyn Iterator<Item = u8>| {
+                    String::from_utf8(digits.filter(|&b| b != b' ').collect())
+                        .unwrap()
+                        .parse::<u64>()
+                        .unwrap()
+                };
+
+                part1 += total(
+                    op,
+                    numbers
+                        .iter()
+                        .map(|row| read(&mut row[start..end].iter().copied())),
+                );
+                part2 += total(
+                    op,
+                    (start..end).map(|col| read(&mut numbers.iter().map(|row| row[col]))),
+                );
+            }
+            start = end + 1;
+        }
+
+        (part1, part2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     // rustfmt::skip
@@ -127,6 +274,33 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator("123 328 \n 45 6x  \n*   +   ").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+
+        let err = try_generator("123 328 \n 45 64  \n*   -   ").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 5));
+
+        let err = try_generator("123 328 \n 45 64  \n*+  +   ").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 2));
+
+        let err = try_generator("123 328 \n 45 64  \n  + +   ").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+
+        assert_eq!(part1(&generator("1\n+")), 1);
+    }
+
+    #[test]
+    pub fn trimmed_test() {
+        let trimmed = SAMPLE
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(generator(&trimmed), generator(SAMPLE));
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 4277556);
@@ -145,19 +319,38 @@ mod tests {
         assert_eq!(part2(&generator(SAMPLE)), 3263827);
     }
 
-    mod regression {
-        use super::*;
-
-        const INPUT: &str = include_str!("../input/2025/day6.txt");
-        const ANSWERS: (u64, u64) = (4387670995909, 9625320374409);
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::solve(SAMPLE), (4277556, 3263827));
+
+        let synth = crate::synth::synth(6).unwrap();
+        for seed in 0..50 {
+            let input = synth.input(seed, 20);
+            let worksheet = generator(&input);
+            assert_eq!(
+                (part1(&worksheet), part2(&worksheet)),
+                oracle::solve(&input),
+                "{input}"
+            );
+        }
+    }
 
+    proptest! {
         #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
-
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+        fn problems_add_up_test(seed in any::<u64>(), left in 1..20usize, right in 1..20usize) {
+            // The grand total of two worksheets side by side is the sum of theirs
+            let synth = crate::synth::synth(6).unwrap();
+            let (a, b) = (synth.input(seed, left), synth.input(seed ^ 1, right));
+            let joined = a
+                .lines()
+                .zip(b.lines())
+                .map(|(a, b)| format!("{a} {b}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let (a, b, joined) = (generator(&a), generator(&b), generator(&joined));
+            prop_assert_eq!(part1(&joined), part1(&a) + part1(&b));
+            prop_assert_eq!(part2(&joined), part2(&a) + part2(&b));
         }
     }
 }
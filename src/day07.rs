@@ -1,36 +1,64 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    error::ParseError,
+    grid::Grid,
+    inputs::normalize,
+    solution::{Answer, Solution},
+};
+
 // We process every other row because those are the rows with splitters
 const ROW_STEP: usize = 2;
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct Map {
-    data: Vec<u8>,
-    width: usize,
-    height: usize,
+pub type Map = Grid<u8>;
+
+pub fn try_generator(input: &str) -> Result<Map, ParseError> {
+    if input.is_empty() {
+        return Err(ParseError::new(7, input, 0, "expected a manifold diagram"));
+    }
+
+    let map = Grid::parse(7, input, "expected '.', 'S' or '^'", |b| {
+        matches!(b, b'.' | b'S' | b'^').then_some(b)
+    })?;
+    if map.position(|&b| b == b'S').is_none() {
+        return Err(ParseError::new(7, input, 0, "expected a start 'S'"));
+    }
+    // A splitter sends beams to both sides, so it can't be on an edge
+    let last = map.width() - 1;
+    if let Some((row, col)) = map
+        .positions()
+        .find(|&(row, col)| (col == 0 || col == last) && map[(row, col)] == b'^')
+    {
+        let line = input.lines().nth(row).unwrap_or_default();
+        return Err(ParseError::at(
+            7,
+            input,
+            &line[col..],
+            "expected no splitter at the edge",
+        ));
+    }
+
+    Ok(map)
 }
 
 #[aoc_generator(day7)]
 pub fn generator(input: &str) -> Map {
-    let data = input.as_bytes().to_vec();
-    let width = input.lines().next().unwrap().len();
-    let height = input.lines().count();
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
+}
 
-    Map {
-        data,
-        width,
-        height,
-    }
+fn start(map: &Map) -> (usize, usize) {
+    map.position(|&b| b == b'S').unwrap()
 }
 
 #[aoc(day7, part1)]
 pub fn part1(map: &Map) -> usize {
+    let (start_row, start_col) = start(map);
     let mut count: usize = 0;
-    let mut beams = Vec::with_capacity(map.width);
-    let mut new_beams = Vec::with_capacity(map.width);
-    new_beams.push(map.width / 2);
+    let mut beams = Vec::with_capacity(map.width());
+    let mut new_beams = Vec::with_capacity(map.width());
+    new_beams.push(start_col);
 
-    for row in (ROW_STEP..map.height).step_by(ROW_STEP) {
+    for row in (start_row + ROW_STEP..map.height()).step_by(ROW_STEP) {
         std::mem::swap(&mut beams, &mut new_beams);
         new_beams.clear();
 
@@ -39,7 +67,7 @@ pub fn part1(map: &Map) -> usize {
         beams.dedup();
 
         for &beam in beams.iter() {
-            if map.data[(map.width + 1) * row + beam] == b'^' {
+            if map[(row, beam)] == b'^' {
                 new_beams.push(beam + 1);
                 new_beams.push(beam - 1);
                 count += 1;
@@ -52,35 +80,88 @@ pub fn part1(map: &Map) -> usize {
     count
 }
 
-fn num_worlds(col: usize, row: usize, map: &Map, memo: &mut Vec<usize>) -> usize {
-    if row >= map.height {
+fn num_worlds(col: usize, row: usize, map: &Map, memo: &mut Grid<usize>) -> usize {
+    if row >= map.height() {
         return 1;
     }
 
-    let index = row * (map.width + 1) + col;
-    let x = memo[index];
+    let x = memo[(row, col)];
     if x > 0 {
         return x;
     }
 
-    let row = row + ROW_STEP;
-    let count = if map.data[index] == b'^' {
-        num_worlds(col + 1, row, map, memo) + num_worlds(col - 1, row, map, memo)
+    let next = row + ROW_STEP;
+    let count = if map[(row, col)] == b'^' {
+        num_worlds(col + 1, next, map, memo) + num_worlds(col - 1, next, map, memo)
     } else {
-        num_worlds(col, row, map, memo)
+        num_worlds(col, next, map, memo)
     };
 
-    memo[index] = count;
+    memo[(row, col)] = count;
     count
 }
 
 #[aoc(day7, part2)]
 pub fn part2(map: &Map) -> usize {
-    num_worlds(map.width / 2, ROW_STEP, map, &mut vec![0; map.data.len()])
+    let (row, col) = start(map);
+    let mut memo = Grid::filled(map.width(), map.height(), 0);
+
+    num_worlds(col, row + ROW_STEP, map, &mut memo)
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 7;
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(map: &Self::Parsed) -> Answer {
+        part1(map).into()
+    }
+
+    fn part2(map: &Self::Parsed) -> Answer {
+        part2(map).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Follows the timelines in every column down every row.
+
+    pub fn solve(input: &str) -> (usize, usize) {
+        let rows = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let mut timelines = rows[0]
+            .iter()
+            .map(|&b| usize::from(b == b'S'))
+            .collect::<Vec<_>>();
+        let mut splits = 0;
+
+        for row in &rows[1..] {
+            let mut next = vec![0; row.len()];
+            for (col, &count) in timelines.iter().enumerate() {
+                if count > 0 && row[col] == b'^' {
+                    splits += 1;
+                    next[col - 1] += count;
+                    next[col + 1] += count;
+                } else {
+                    next[col] += count;
+                }
+            }
+            timelines = next;
+        }
+
+        (splits, timelines.iter().sum())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     const SAMPLE: &str = r".......S.......
@@ -107,6 +188,23 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator("..S..\n.....\n..v..").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+
+        let err = try_generator("..S..\n....").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = try_generator(".....\n..^..").unwrap_err();
+        assert_eq!(err.message, "expected a start 'S'");
+
+        let err = try_generator("S\n.\n^").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        let err = try_generator("..S..\n.....\n....^").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 5));
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 21);
@@ -117,19 +215,26 @@ mod tests {
         assert_eq!(part2(&generator(SAMPLE)), 40);
     }
 
-    mod regression {
-        use super::*;
-
-        const INPUT: &str = include_str!("../input/2025/day7.txt");
-        const ANSWERS: (usize, usize) = (1717, 231507396180012);
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::solve(SAMPLE), (21, 40));
+
+        let synth = crate::synth::synth(7).unwrap();
+        for seed in 0..50 {
+            let input = synth.input(seed, 5 + seed as usize % 20);
+            let map = generator(&input);
+            assert_eq!((part1(&map), part2(&map)), oracle::solve(&input), "{input}");
+        }
+    }
 
+    proptest! {
         #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
-
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+        fn splits_at_most_splitters_test(seed in any::<u64>(), size in 1..60usize) {
+            // Each splitter splits at most once, and a beam always ends somewhere
+            let input = crate::synth::synth(7).unwrap().input(seed, size);
+            let map = generator(&input);
+            prop_assert!(part1(&map) <= input.matches('^').count());
+            prop_assert!(part2(&map) >= 1);
         }
     }
 }
@@ -1,8 +1,15 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
-use nom::{IResult, Parser, bytes::complete::tag};
+use nom::{IResult, Parser, bytes::complete::tag, combinator::all_consuming};
 
-use crate::common::nom::{nom_lines, nom_usize, process_input};
+use crate::{
+    common::nom::nom_usize,
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Solution},
+    trace,
+    union_find::UnionFind,
+};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Coords {
@@ -11,50 +18,8 @@ pub struct Coords {
     z: usize,
 }
 
-struct UnionFind {
-    parent: Vec<usize>,
-    rank: Vec<usize>,
-}
-
-impl UnionFind {
-    fn new(n: usize) -> Self {
-        UnionFind {
-            parent: (0..n).collect(),
-            rank: vec![0; n],
-        }
-    }
-
-    fn find_path_halving(&mut self, mut x: usize) -> usize {
-        while self.parent[x] != x {
-            self.parent[x] = self.parent[self.parent[x]];
-            x = self.parent[x];
-        }
-
-        x
-    }
-
-    // This implements path halving which was faster than path compression
-    // and path splitting for both parts.
-    fn union_path_halving(&mut self, x: usize, y: usize) -> bool {
-        let px = self.find_path_halving(x);
-        let py = self.find_path_halving(y);
-        if px == py {
-            return false;
-        }
-
-        // Union by rank
-        match self.rank[px].cmp(&self.rank[py]) {
-            std::cmp::Ordering::Less => self.parent[px] = py,
-            std::cmp::Ordering::Greater => self.parent[py] = px,
-            std::cmp::Ordering::Equal => {
-                self.parent[px] = py;
-                self.rank[py] += 1;
-            }
-        }
-
-        true
-    }
-}
+/// Junction boxes, and every pair of them as (distance, i, j) sorted by distance.
+type Playground = (Vec<Coords>, Vec<(usize, usize, usize)>);
 
 fn euclidean_distance(coord_a: &Coords, coord_b: &Coords) -> usize {
     let dx = coord_a.x.abs_diff(coord_b.x);
@@ -74,9 +39,25 @@ fn parse_coords(s: &str) -> IResult<&str, Coords> {
     Ok((s, Coords { x, y, z }))
 }
 
-#[aoc_generator(day8)]
-pub fn generator(input: &str) -> (Vec<Coords>, Vec<(usize, usize, usize)>) {
-    let inputs = process_input(nom_lines(parse_coords))(input);
+pub fn try_generator(input: &str) -> Result<Playground, ParseError> {
+    let inputs = input
+        .lines()
+        .map(|line| {
+            all_consuming(parse_coords)
+                .parse(line)
+                .map(|(_, coords)| coords)
+                .map_err(|e| ParseError::from_nom(8, input, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // Part 2 needs a last pair to connect
+    if inputs.len() < 2 {
+        return Err(ParseError::new(
+            8,
+            input,
+            input.len(),
+            "expected at least two junction boxes",
+        ));
+    }
 
     // find the pairs coordinates that are the closest to another coordinate
     let mut pairs = Vec::with_capacity(inputs.len() * (inputs.len() - 1) / 2);
@@ -92,24 +73,25 @@ pub fn generator(input: &str) -> (Vec<Coords>, Vec<(usize, usize, usize)>) {
 
     pairs.sort_unstable_by_key(|&(dist, _, _)| dist);
 
-    (inputs, pairs)
+    Ok((inputs, pairs))
+}
+
+#[aoc_generator(day8)]
+pub fn generator(input: &str) -> Playground {
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 fn part1_solve<const COUNT: usize>(inputs: &[Coords], pairs: &[(usize, usize, usize)]) -> usize {
     let mut uf = UnionFind::new(inputs.len());
     for (_, i, j) in pairs.iter().take(COUNT).copied() {
-        uf.union_path_halving(i, j);
-    }
-
-    // build circuit counts from parent groups
-    let mut circuits = vec![0; inputs.len()];
-    for i in 0..inputs.len() {
-        circuits[uf.find_path_halving(i)] += 1;
+        if uf.union(i, j) {
+            trace!("union", a = i, b = j, components = uf.components());
+        }
     }
 
     // Get top 3 without full sort
     let mut top3 = [0usize; 3];
-    for c in circuits {
+    for c in uf.sizes() {
         if c > top3[0] {
             top3[2] = top3[1];
             top3[1] = top3[0];
@@ -125,22 +107,20 @@ fn part1_solve<const COUNT: usize>(inputs: &[Coords], pairs: &[(usize, usize, us
 }
 
 #[aoc(day8, part1)]
-pub fn part1((inputs, pairs): &(Vec<Coords>, Vec<(usize, usize, usize)>)) -> usize {
+pub fn part1((inputs, pairs): &Playground) -> usize {
     part1_solve::<1000>(inputs, pairs)
 }
 
 #[aoc(day8, part2)]
-pub fn part2((inputs, pairs): &(Vec<Coords>, Vec<(usize, usize, usize)>)) -> usize {
+pub fn part2((inputs, pairs): &Playground) -> usize {
     let mut last_union = (0, 1);
     let mut uf = UnionFind::new(inputs.len());
-    let mut unions_done = 0;
-    let target_unions = inputs.len() - 1;
 
     for (_, i, j) in pairs.iter().copied() {
-        if uf.union_path_halving(i, j) {
+        if uf.union(i, j) {
             last_union = (i, j);
-            unions_done += 1;
-            if unions_done == target_unions {
+            trace!("union", a = i, b = j, components = uf.components());
+            if uf.components() == 1 {
                 break;
             }
         }
@@ -150,6 +130,94 @@ pub fn part2((inputs, pairs): &(Vec<Coords>, Vec<(usize, usize, usize)>)) -> usi
     inputs[last_union.0].x * inputs[last_union.1].x
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 8;
+    type Parsed = Playground;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Labels every box with its circuit, and relabels a whole circuit on
+    //! every connection.
+
+    fn boxes(input: &str) -> Vec<[u64; 3]> {
+        input
+            .lines()
+            .map(|line| {
+                let mut coords = line.split(',').map(|n| n.parse().unwrap());
+                [(); 3].map(|()| coords.next().unwrap())
+            })
+            .collect()
+    }
+
+    fn closest(boxes: &[[u64; 3]]) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for i in 0..boxes.len() {
+            for j in i + 1..boxes.len() {
+                pairs.push((i, j));
+            }
+        }
+        pairs.sort_by_key(|&(i, j)| {
+            (0..3)
+                .map(|axis| boxes[i][axis].abs_diff(boxes[j][axis]).pow(2))
+                .sum::<u64>()
+        });
+        pairs
+    }
+
+    /// Merges the circuits of `i` and `j`, returning whether they were apart.
+    fn connect(circuit: &mut [usize], i: usize, j: usize) -> bool {
+        let (from, to) = (circuit[j], circuit[i]);
+        for label in circuit.iter_mut().filter(|label| **label == from) {
+            *label = to;
+        }
+        from != to
+    }
+
+    pub fn part1(input: &str, connections: usize) -> usize {
+        let boxes = boxes(input);
+        let mut circuit = (0..boxes.len()).collect::<Vec<_>>();
+        for (i, j) in closest(&boxes).into_iter().take(connections) {
+            connect(&mut circuit, i, j);
+        }
+
+        let mut sizes = (0..boxes.len())
+            .map(|label| circuit.iter().filter(|&&c| c == label).count())
+            .collect::<Vec<_>>();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes[..3].iter().product()
+    }
+
+    pub fn part2(input: &str) -> u64 {
+        let boxes = boxes(input);
+        let mut circuit = (0..boxes.len()).collect::<Vec<_>>();
+        let mut circuits = boxes.len();
+        for (i, j) in closest(&boxes) {
+            if connect(&mut circuit, i, j) {
+                circuits -= 1;
+                if circuits == 1 {
+                    return boxes[i][0] * boxes[j][0];
+                }
+            }
+        }
+        unreachable!("the boxes were never all connected")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,6 +250,16 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator("162,817,812\n57,618;57").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 7));
+
+        let err = try_generator("1,2,3").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 6));
+        assert!(crate::solve(8, crate::solution::Part::Two, "1,2,3").is_err());
+    }
+
     #[test]
     pub fn part1_test() {
         let (a, b) = &generator(SAMPLE);
@@ -193,19 +271,25 @@ mod tests {
         assert_eq!(part2(&generator(SAMPLE)), 25272);
     }
 
-    mod regression {
-        use super::*;
-
-        const INPUT: &str = include_str!("../input/2025/day8.txt");
-        const ANSWERS: (usize, usize) = (98696, 2245203960);
-
-        #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
-
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::part1(SAMPLE, 10), 40);
+        assert_eq!(oracle::part2(SAMPLE), 25272);
+
+        let synth = crate::synth::synth(8).unwrap();
+        for seed in 0..50 {
+            let input = synth.input(seed, 30);
+            let (boxes, pairs) = &generator(&input);
+            assert_eq!(
+                part1_solve::<10>(boxes, pairs),
+                oracle::part1(&input, 10),
+                "{input}"
+            );
+            assert_eq!(
+                part2(&generator(&input)) as u64,
+                oracle::part2(&input),
+                "{input}"
+            );
         }
     }
 }
@@ -2,15 +2,32 @@ use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 use nom::{IResult, Parser, bytes::complete::tag, combinator, sequence::separated_pair};
 
-use crate::common::nom::{nom_i64, nom_lines, process_input};
+use crate::{
+    common::nom::nom_i64,
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Solution},
+};
 
 fn parse_line(s: &str) -> IResult<&str, [i64; 2]> {
     combinator::map(separated_pair(nom_i64, tag(","), nom_i64), |(a, b)| [a, b]).parse(s)
 }
 
+pub fn try_generator(input: &str) -> Result<Vec<[i64; 2]>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            combinator::all_consuming(parse_line)
+                .parse(line)
+                .map(|(_, tile)| tile)
+                .map_err(|e| ParseError::from_nom(9, input, e))
+        })
+        .collect()
+}
+
 #[aoc_generator(day9)]
 pub fn generator(input: &str) -> Vec<[i64; 2]> {
-    process_input(nom_lines(parse_line))(input)
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 #[aoc(day9, part1)]
@@ -27,99 +44,192 @@ fn distance(a: [i64; 2], b: [i64; 2]) -> u64 {
     (a[0].abs_diff(b[0]) + 1) * (a[1].abs_diff(b[1]) + 1)
 }
 
-fn sort_pairs(x: [i64; 2], y: [i64; 2]) -> [[i64; 2]; 2] {
-    let a = [x[0].min(y[0]), x[1].min(y[1])];
-    let b = [x[0].max(y[0]), x[1].max(y[1])];
-
-    [a, b]
+/// The floor on a compressed grid: a row and column for every distinct
+/// coordinate of a red tile, one for the gap up to the next, and padding
+/// around the outside.
+struct Floor {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    /// Prefix sums over the compressed cells holding tiles that are neither
+    /// red nor green.
+    outside: Vec<Vec<u32>>,
 }
 
-/// Edge represented as (fixed_coord, range_min, range_max)
-type Edge = (i64, i64, i64);
+impl Floor {
+    fn new(red: &[[i64; 2]]) -> Self {
+        const UNKNOWN: u8 = 0;
+        const LOOP: u8 = 1;
+        const OUTSIDE: u8 = 2;
 
-/// Vertical edge: (x, y_min, y_max)
-/// Horizontal edge: (y, x_min, x_max)
-fn build_edge_lists(inputs: &[[i64; 2]]) -> (Vec<Edge>, Vec<Edge>) {
-    let n = inputs.len();
-    let mut vertical = Vec::with_capacity(n / 2);
-    let mut horizontal = Vec::with_capacity(n / 2);
+        let xs: Vec<i64> = red.iter().map(|t| t[0]).sorted_unstable().dedup().collect();
+        let ys: Vec<i64> = red.iter().map(|t| t[1]).sorted_unstable().dedup().collect();
+        let (width, height) = (2 * xs.len() + 1, 2 * ys.len() + 1);
+        let mut cells = vec![vec![UNKNOWN; width]; height];
 
-    // loop through edge pairs and process each edge into vertical and horizontal lists
-    // this works like circular_tuple_windows()
-    for i in 0..(n - 1) {
-        process_edge(&mut vertical, &mut horizontal, inputs[i], inputs[i + 1]);
-    }
-    process_edge(&mut vertical, &mut horizontal, inputs[n - 1], inputs[0]);
+        let floor = Floor {
+            xs,
+            ys,
+            outside: Vec::new(),
+        };
 
-    debug_assert_eq!(vertical.capacity(), n / 2);
-    debug_assert_eq!(horizontal.capacity(), n / 2);
-    debug_assert_eq!(vertical.len(), horizontal.len());
+        for (a, b) in red.iter().circular_tuple_windows() {
+            let [ax, ay] = floor.index(*a);
+            let [bx, by] = floor.index(*b);
 
-    vertical.sort_unstable();
-    horizontal.sort_unstable();
+            for row in &mut cells[ay.min(by)..=ay.max(by)] {
+                row[ax.min(bx)..=ax.max(bx)].fill(LOOP);
+            }
+        }
 
-    (vertical, horizontal)
-}
+        // The padding is outside the loop, so flood fill from a corner of it
+        let mut stack = vec![(0usize, 0usize)];
+        cells[0][0] = OUTSIDE;
+        while let Some((x, y)) = stack.pop() {
+            let neighbors = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
 
-fn process_edge(vertical: &mut Vec<Edge>, horizontal: &mut Vec<Edge>, p1: [i64; 2], p2: [i64; 2]) {
-    if p1[0] == p2[0] {
-        // Vertical edge
-        vertical.push((p1[0], p1[1].min(p2[1]), p1[1].max(p2[1])));
-    } else {
-        // Horizontal edge
-        horizontal.push((p1[1], p1[0].min(p2[0]), p1[0].max(p2[0])));
-    }
-}
-
-/// Checks if any edge crosses through the interior of a box.
-/// Edges are sorted by fixed coordinate for early termination.
-fn any_edge_intersects(
-    edges: &[Edge],
-    fixed_min: i64,
-    fixed_max: i64,
-    range_min: i64,
-    range_max: i64,
-) -> bool {
-    for &(fixed, e_min, e_max) in edges {
-        if fixed <= fixed_min {
-            continue;
-        }
-        if fixed >= fixed_max {
-            break;
+            for (nx, ny) in neighbors {
+                if nx < width && ny < height && cells[ny][nx] == UNKNOWN {
+                    cells[ny][nx] = OUTSIDE;
+                    stack.push((nx, ny));
+                }
+            }
         }
-        if e_min < range_max && e_max > range_min {
-            return true;
+
+        // A gap between adjacent coordinates holds no tiles, so it can't hold outside ones
+        let empty = |coords: &[i64], i: usize| {
+            i.is_multiple_of(2) && coords[i / 2] - coords[i / 2 - 1] == 1
+        };
+
+        let mut outside = vec![vec![0; width + 1]; height + 1];
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                let bad = cells[y][x] == OUTSIDE && !empty(&floor.xs, x) && !empty(&floor.ys, y);
+                outside[y + 1][x + 1] =
+                    u32::from(bad) + outside[y][x + 1] + outside[y + 1][x] - outside[y][x];
+            }
         }
+
+        Floor { outside, ..floor }
+    }
+
+    /// The compressed cell of a red tile.
+    fn index(&self, [x, y]: [i64; 2]) -> [usize; 2] {
+        let find = |coords: &[i64], c| 2 * coords.binary_search(&c).unwrap() + 1;
+
+        [find(&self.xs, x), find(&self.ys, y)]
+    }
+
+    /// Whether every tile in the rectangle between two red tiles is red or green.
+    fn covers(&self, [ax, ay]: [usize; 2], [bx, by]: [usize; 2]) -> bool {
+        let (x0, x1) = (ax.min(bx), ax.max(bx) + 1);
+        let (y0, y1) = (ay.min(by), ay.max(by) + 1);
+        let p = &self.outside;
+
+        p[y1][x1] + p[y0][x0] == p[y0][x1] + p[y1][x0]
     }
-    false
 }
 
 #[aoc(day9, part2)]
 pub fn part2(inputs: &[[i64; 2]]) -> u64 {
-    let (vertical, horizontal) = build_edge_lists(inputs);
+    let floor = Floor::new(inputs);
+    let cells: Vec<_> = inputs.iter().map(|&tile| floor.index(tile)).collect();
 
     inputs
         .iter()
+        .zip(&cells)
         .tuple_combinations()
-        .filter_map(|(p1, p2)| {
-            let [a, b] = sort_pairs(*p1, *p2);
-
-            // Check if any edge crosses through the rectangle
-            if any_edge_intersects(&vertical, a[0], b[0], a[1], b[1])
-                || any_edge_intersects(&horizontal, a[1], b[1], a[0], b[0])
-            {
-                None
-            } else {
-                Some(distance(a, b))
-            }
-        })
+        .filter(|((_, a), (_, b))| floor.covers(**a, **b))
+        .map(|((a, _), (b, _))| distance(*a, *b))
         .max()
         .unwrap_or(0)
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 9;
+    type Parsed = Vec<[i64; 2]>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(inputs: &Self::Parsed) -> Answer {
+        part1(inputs).into()
+    }
+
+    fn part2(inputs: &Self::Parsed) -> Answer {
+        part2(inputs).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Checks every tile of every rectangle against the polygon.
+
+    fn tiles(input: &str) -> Vec<[i64; 2]> {
+        input
+            .lines()
+            .map(|line| {
+                let (x, y) = line.split_once(',').unwrap();
+                [x.parse().unwrap(), y.parse().unwrap()]
+            })
+            .collect()
+    }
+
+    /// Whether a tile is red or green: on the loop, or inside it.
+    fn inside(red: &[[i64; 2]], [x, y]: [i64; 2]) -> bool {
+        let mut inside = false;
+
+        for (i, &a) in red.iter().enumerate() {
+            let b = red[(i + 1) % red.len()];
+            let (x0, x1) = (a[0].min(b[0]), a[0].max(b[0]));
+            let (y0, y1) = (a[1].min(b[1]), a[1].max(b[1]));
+
+            if (x0..=x1).contains(&x) && (y0..=y1).contains(&y) {
+                return true;
+            }
+            // Cast a ray to the right, counting the vertical edges it crosses
+            if x0 == x1 && x0 > x && (y0..y1).contains(&y) {
+                inside = !inside;
+            }
+        }
+
+        inside
+    }
+
+    pub fn solve(input: &str) -> (u64, u64) {
+        let red = tiles(input);
+        let (mut part1, mut part2) = (0, 0);
+
+        for (i, a) in red.iter().enumerate() {
+            for b in &red[i + 1..] {
+                let (x0, x1) = (a[0].min(b[0]), a[0].max(b[0]));
+                let (y0, y1) = (a[1].min(b[1]), a[1].max(b[1]));
+                let area = (x1 - x0 + 1) as u64 * (y1 - y0 + 1) as u64;
+
+                part1 = part1.max(area);
+                if area > part2 && (x0..=x1).all(|x| (y0..=y1).all(|y| inside(&red, [x, y]))) {
+                    part2 = area;
+                }
+            }
+        }
+
+        (part1, part2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::rng::Rng;
 
     const SAMPLE: &str = r"7,1
 11,1
@@ -137,6 +247,12 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator("7,1\n11,1\n11 7").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 50);
@@ -147,19 +263,53 @@ mod tests {
         assert_eq!(part2(&generator(SAMPLE)), 24);
     }
 
-    mod regression {
-        use super::*;
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::solve(SAMPLE), (50, 24));
 
-        const INPUT: &str = include_str!("../input/2025/day9.txt");
-        const ANSWERS: (u64, u64) = (4771532800, 1544362560);
+        for seed in 0..200 {
+            // A histogram of bars with gaps as narrow as one tile, in any orientation
+            let mut rng = Rng::new(seed);
+            let (mut x, mut height) = (0, 0);
+            let mut draw = |range| rng.range(range) as i64;
+            let mut red = vec![[0, 0]];
+            for _ in 0..draw(2..=6) {
+                height = (height + draw(0..=8)) % 10 + 1;
+                red.push([x, height]);
+                x += draw(1..=5);
+                red.push([x, height]);
+            }
+            red.push([x, 0]);
 
-        #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
+            let (swap, flip) = (rng.chance(0.5), rng.chance(0.5));
+            let input = red
+                .iter()
+                .map(|&[x, y]| {
+                    let [x, y] = if swap { [y, x] } else { [x, y] };
+                    if flip {
+                        format!("{},{y}", 40 - x)
+                    } else {
+                        format!("{x},{y}")
+                    }
+                })
+                .join("\n");
 
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+            let tiles = generator(&input);
+            assert_eq!(
+                (part1(&tiles), part2(&tiles)),
+                oracle::solve(&input),
+                "{input}"
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn inside_at_most_any_test(seed in any::<u64>(), size in 4..100usize) {
+            // A rectangle inside the loop is still a rectangle between red tiles
+            let tiles = generator(&crate::synth::synth(9).unwrap().input(seed, size));
+            prop_assert!(part2(&tiles) <= part1(&tiles));
+            prop_assert!(part2(&tiles) >= 1);
         }
     }
 }
@@ -5,12 +5,19 @@ use nom::{
     IResult, Parser,
     bytes::complete::tag,
     character::complete::{one_of, space1},
+    combinator::{all_consuming, verify},
     multi::{fold_many0, separated_list0},
     sequence::delimited,
 };
 use rustc_hash::FxHashMap as HashMap;
 
-use crate::common::nom::{fold_separated_list0, nom_lines, nom_u16, nom_usize, process_input};
+use crate::{
+    common::nom::{fold_separated_list0, nom_u16, nom_usize},
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Solution},
+    trace,
+};
 
 const MAX_BUTTONS: usize = 16;
 type ButtonEffect = [u16; MAX_BUTTONS];
@@ -240,6 +247,12 @@ fn solve_p2(btn_coeffs: &[u16], goal: &[u16]) -> usize {
                     // Recurse with new goal, multiply cost by 2
                     if let Some(recursed_cost) = solve_aux(new_goal, effect_cost_lookup, cache) {
                         let cost = cost + recursed_cost * 2;
+                        trace!(
+                            "choice",
+                            goal = goal.as_slice(),
+                            effect = effect,
+                            cost = cost
+                        );
                         answer = Some(answer.map_or(cost, |x| cost.min(x)));
                     }
                 }
@@ -253,20 +266,27 @@ fn solve_p2(btn_coeffs: &[u16], goal: &[u16]) -> usize {
     solve_aux(goal_arr, &effect_costs, &mut cache).unwrap_or(usize::MAX)
 }
 
-fn parse_target_indicator(s: &str) -> IResult<&str, u16> {
-    delimited(
-        tag("["),
-        fold_many0(
-            one_of("#."),
-            || (0u16, 0usize),
-            |(acc, idx), c| {
-                let new_acc = if c == '#' { acc | (1 << idx) } else { acc };
-                (new_acc, idx + 1)
-            },
+/// The lights to turn on, and how many lights there are.
+fn parse_target_indicator(s: &str) -> IResult<&str, (u16, usize)> {
+    verify(
+        delimited(
+            tag("["),
+            fold_many0(
+                one_of("#."),
+                || (0u16, 0usize),
+                |(acc, idx), c| {
+                    let new_acc = if c == '#' {
+                        acc | 1u16.checked_shl(idx as u32).unwrap_or(0)
+                    } else {
+                        acc
+                    };
+                    (new_acc, idx + 1)
+                },
+            ),
+            tag("]"),
         ),
-        tag("]"),
+        |&(_, lights)| lights <= MAX_BUTTONS,
     )
-    .map(|(acc, _)| acc)
     .parse(s)
 }
 
@@ -275,7 +295,12 @@ fn parse_buttons(s: &str) -> IResult<&str, Vec<u16>> {
         tag(" "),
         delimited(
             tag("("),
-            fold_separated_list0(tag(","), nom_usize, || 0u16, |acc, idx| acc | (1 << idx)),
+            fold_separated_list0(
+                tag(","),
+                verify(nom_usize, |&idx| idx < MAX_BUTTONS),
+                || 0u16,
+                |acc, idx| acc | (1 << idx),
+            ),
             tag(")"),
         ),
     )
@@ -283,19 +308,29 @@ fn parse_buttons(s: &str) -> IResult<&str, Vec<u16>> {
 }
 
 fn parse_joltages(s: &str) -> IResult<&str, ArrayVec<u16, MAX_BUTTONS>> {
-    delimited(
-        tag("{"),
-        fold_separated_list0(tag(","), nom_u16, ArrayVec::new, |mut acc, item| {
-            acc.push(item);
-            acc
-        }),
-        tag("}"),
+    verify(
+        delimited(
+            tag("{"),
+            fold_separated_list0(
+                tag(","),
+                nom_u16,
+                || (ArrayVec::new(), 0usize),
+                |(mut acc, n), item| {
+                    let _ = acc.try_push(item);
+                    (acc, n + 1)
+                },
+            ),
+            tag("}"),
+        ),
+        |&(_, n)| n <= MAX_BUTTONS,
     )
+    .map(|(acc, _)| acc)
     .parse(s)
 }
 
-fn parse_machine(s: &str) -> IResult<&str, Machine> {
-    let (s, target_indicator) = parse_target_indicator(s)?;
+/// A machine, and how many lights it has.
+fn parse_machine(s: &str) -> IResult<&str, (Machine, usize)> {
+    let (s, (target_indicator, lights)) = parse_target_indicator(s)?;
     let (s, _) = space1(s)?;
     let (s, buttons) = parse_buttons(s)?;
     let (s, _) = space1(s)?;
@@ -303,24 +338,72 @@ fn parse_machine(s: &str) -> IResult<&str, Machine> {
 
     Ok((
         s,
-        Machine {
-            target_indicator,
-            buttons,
-            joltages,
-        },
+        (
+            Machine {
+                target_indicator,
+                buttons,
+                joltages,
+            },
+            lights,
+        ),
     ))
 }
 
+pub fn try_generator(input: &str) -> Result<Vec<Machine>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let (machine, lights) = all_consuming(parse_machine)
+                .parse(line)
+                .map(|(_, machine)| machine)
+                .map_err(|e| ParseError::from_nom(10, input, e))?;
+
+            // Buttons and joltages must only refer to lights the machine has
+            if let Some(i) = machine
+                .buttons
+                .iter()
+                .position(|&wiring| u32::from(wiring) >> lights != 0)
+            {
+                let button = line
+                    .match_indices('(')
+                    .nth(i)
+                    .map_or(line, |(pos, _)| &line[pos..]);
+                return Err(ParseError::at(
+                    10,
+                    input,
+                    button,
+                    format!("expected lights below {lights}"),
+                ));
+            }
+            if machine.joltages.len() != lights {
+                let joltages = line.find('{').map_or(line, |pos| &line[pos..]);
+                return Err(ParseError::at(
+                    10,
+                    input,
+                    joltages,
+                    format!("expected {lights} joltages"),
+                ));
+            }
+
+            Ok(machine)
+        })
+        .collect()
+}
+
 #[aoc_generator(day10)]
 pub fn generator(input: &str) -> Vec<Machine> {
-    process_input(nom_lines(parse_machine))(input)
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 #[aoc(day10, part1)]
 pub fn part1(inputs: &[Machine]) -> usize {
     inputs
         .iter()
-        .map(|m| fewest_p1(m.target_indicator, &m.buttons))
+        .map(|m| {
+            let presses = fewest_p1(m.target_indicator, &m.buttons);
+            trace!("machine", indicator = m.target_indicator, presses = presses);
+            presses
+        })
         .sum()
 }
 
@@ -328,13 +411,132 @@ pub fn part1(inputs: &[Machine]) -> usize {
 pub fn part2(inputs: &[Machine]) -> usize {
     inputs
         .iter()
-        .map(|m| solve_p2(&m.buttons, &m.joltages))
+        .map(|m| {
+            let presses = solve_p2(&m.buttons, &m.joltages);
+            trace!(
+                "machine",
+                joltages = m.joltages.as_slice(),
+                presses = presses
+            );
+            presses
+        })
         .sum()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 10;
+    type Parsed = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(inputs: &Self::Parsed) -> Answer {
+        part1(inputs).into()
+    }
+
+    fn part2(inputs: &Self::Parsed) -> Answer {
+        part2(inputs).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Tries every set of buttons for the indicator lights, and every number
+    //! of presses of every button for the joltages.
+
+    pub struct Machine {
+        indicator: Vec<bool>,
+        buttons: Vec<Vec<usize>>,
+        joltages: Vec<u64>,
+    }
+
+    pub fn machines(input: &str) -> Vec<Machine> {
+        input
+            .lines()
+            .map(|line| {
+                let fields = line.split(' ').collect::<Vec<_>>();
+                let (indicator, rest) = fields.split_first().unwrap();
+                let (joltages, buttons) = rest.split_last().unwrap();
+                let list = |s: &str| {
+                    s[1..s.len() - 1]
+                        .split(',')
+                        .filter(|n| !n.is_empty())
+                        .map(|n| n.parse().unwrap())
+                        .collect::<Vec<_>>()
+                };
+
+                Machine {
+                    indicator: indicator[1..indicator.len() - 1]
+                        .chars()
+                        .map(|c| c == '#')
+                        .collect(),
+                    buttons: buttons.iter().map(|b| list(b)).collect(),
+                    joltages: list(joltages).into_iter().map(|j| j as u64).collect(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn fewest_toggles(machine: &Machine) -> usize {
+        (0u32..1 << machine.buttons.len())
+            .filter(|&mask| {
+                let mut lights = vec![false; machine.indicator.len()];
+                for (i, wiring) in machine.buttons.iter().enumerate() {
+                    if mask & 1 << i != 0 {
+                        for &light in wiring {
+                            lights[light] ^= true;
+                        }
+                    }
+                }
+                lights == machine.indicator
+            })
+            .map(|mask| mask.count_ones() as usize)
+            .min()
+            .unwrap()
+    }
+
+    /// The fewest presses of `buttons[button..]` that take `joltages` to zero.
+    fn presses(buttons: &[Vec<usize>], button: usize, joltages: &mut [u64]) -> Option<u64> {
+        let Some(wiring) = buttons.get(button) else {
+            return joltages.iter().all(|&j| j == 0).then_some(0);
+        };
+
+        let most = wiring
+            .iter()
+            .map(|&light| joltages[light])
+            .min()
+            .unwrap_or(0);
+        let mut fewest = None::<u64>;
+        for count in 0..=most {
+            for &light in wiring {
+                joltages[light] -= count;
+            }
+            if let Some(rest) = presses(buttons, button + 1, joltages) {
+                fewest = Some(fewest.map_or(count + rest, |f| f.min(count + rest)));
+            }
+            for &light in wiring {
+                joltages[light] += count;
+            }
+        }
+
+        fewest
+    }
+
+    pub fn fewest_presses(machine: &Machine) -> u64 {
+        presses(&machine.buttons, 0, &mut machine.joltages.clone()).unwrap()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::rng::Rng;
 
     const SAMPLE: &str = r"[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
 [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
@@ -347,6 +549,19 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err =
+            try_generator("[.##.] (3) (1,3) {3,5,4,7}\n[...#.] (0,2) (2;3) {7,5}").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 15));
+
+        let err = try_generator("[.#] (0) (5) {1,2}").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 10));
+        let err = try_generator("[.#] (0) (1) {1,2,3}").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 14));
+        assert!(try_generator("[.................] (0) {1}").is_err());
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 7);
@@ -357,19 +572,75 @@ mod tests {
         assert_eq!(part2(&generator(SAMPLE)), 33);
     }
 
-    mod regression {
-        use super::*;
-
-        const INPUT: &str = include_str!("../input/2025/day10.txt");
-        const ANSWERS: (usize, usize) = (452, 17424);
+    #[test]
+    pub fn oracle_test() {
+        let machines = oracle::machines(SAMPLE);
+        assert_eq!(
+            machines.iter().map(oracle::fewest_toggles).sum::<usize>(),
+            7
+        );
+        assert_eq!(machines.iter().map(oracle::fewest_presses).sum::<u64>(), 33);
+
+        for seed in 0..200 {
+            // Small machines, solvable by construction
+            let mut rng = Rng::new(seed);
+            let lights = rng.range(1..=5) as usize;
+            let buttons = (0..rng.range(1..=6))
+                .map(|_| {
+                    let wiring = (0..lights).filter(|_| rng.chance(0.5)).collect::<Vec<_>>();
+                    if wiring.is_empty() {
+                        vec![rng.index(lights)]
+                    } else {
+                        wiring
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let mut indicator = vec!['.'; lights];
+            let mut joltages = vec![0; lights];
+            for wiring in &buttons {
+                let presses = rng.range(0..=4);
+                for &light in wiring {
+                    if presses % 2 == 1 {
+                        indicator[light] = if indicator[light] == '#' { '.' } else { '#' };
+                    }
+                    joltages[light] += presses;
+                }
+            }
+            let input = format!(
+                "[{}] {} {{{}}}",
+                indicator.iter().collect::<String>(),
+                buttons
+                    .iter()
+                    .map(|wiring| format!("({})", wiring.iter().join(",")))
+                    .join(" "),
+                joltages.iter().join(",")
+            );
+
+            let machine = &oracle::machines(&input)[0];
+            let parsed = generator(&input);
+            assert_eq!(part1(&parsed), oracle::fewest_toggles(machine), "{input}");
+            assert_eq!(
+                part2(&parsed) as u64,
+                oracle::fewest_presses(machine),
+                "{input}"
+            );
+        }
+    }
 
+    proptest! {
         #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
-
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+        fn presses_bounded_test(seed in any::<u64>(), size in 1..10usize) {
+            // Pressing a button twice undoes it, so part 1 never needs more
+            // presses than there are buttons; each press raises a counter by
+            // at most 1, so part 2 needs at least the highest joltage
+            let machines = generator(&crate::synth::synth(10).unwrap().input(seed, size));
+            for machine in &machines {
+                let machine = std::slice::from_ref(machine);
+                prop_assert!(part1(machine) <= machine[0].buttons.len());
+                let highest = machine[0].joltages.iter().copied().max().unwrap_or(0);
+                prop_assert!(part2(machine) >= usize::from(highest));
+            }
         }
     }
 }
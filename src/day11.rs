@@ -1,25 +1,47 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 use rustc_hash::FxHashMap as HashMap;
 
-use crate::common::sstr::SStr;
+use crate::{
+    common::sstr::SStr,
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Solution},
+};
 
 // Using an ArrayVec will increase speed, but unncessary because it's already super fast.
 // type Vec<T> = arrayvec::ArrayVec<T, 32>;
 type String = SStr<3>;
 
-#[aoc_generator(day11)]
-pub fn generator(input: &str) -> HashMap<String, Vec<String>> {
+fn parse_device<'a>(input: &str, name: &'a str) -> Result<&'a str, ParseError> {
+    if name.is_empty() || name.len() > 3 || !name.bytes().all(|b| b.is_ascii_lowercase()) {
+        return Err(ParseError::at(11, input, name, "expected a device name"));
+    }
+
+    Ok(name)
+}
+
+pub fn try_generator(input: &str) -> Result<HashMap<String, Vec<String>>, ParseError> {
     input
         .lines()
         .map(|line| {
-            let (key, rest) = line.split_once(": ").unwrap();
-            let connections = rest.split(' ').map(|s| s.into()).collect::<Vec<_>>();
+            let (key, rest) = line
+                .split_once(": ")
+                .ok_or_else(|| ParseError::at(11, input, line, "expected 'device: outputs'"))?;
+            let connections = rest
+                .split(' ')
+                .map(|s| parse_device(input, s).map(String::from))
+                .collect::<Result<Vec<_>, _>>()?;
 
-            (key.into(), connections)
+            Ok((parse_device(input, key)?.into(), connections))
         })
         .collect()
 }
 
+#[aoc_generator(day11)]
+pub fn generator(input: &str) -> HashMap<String, Vec<String>> {
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
+}
+
 #[aoc(day11, part1)]
 pub fn part1(inputs: &HashMap<String, Vec<String>>) -> usize {
     pathfinding::prelude::count_paths(
@@ -35,7 +57,8 @@ pub fn part1(inputs: &HashMap<String, Vec<String>>) -> usize {
 /// 1. **Encodes state directly in return values** instead of exploring all state combinations:
 ///    - count.0: total paths from this node to "out"
 ///    - count.1: paths that have visited "dac" (node marker)
-///    - count.2: paths that have visited both "dac" AND "fft" (final state)
+///    - count.2: paths that have visited "fft" (node marker)
+///    - count.3: paths that have visited both "dac" AND "fft" (final state), in either order
 ///
 /// 2. **Avoids state explosion**: count_paths would require the use of a tuple to keep track
 ///    of (location, visited_dac, visited_fft) and explores every combination. This creates many
@@ -50,8 +73,8 @@ pub fn part1(inputs: &HashMap<String, Vec<String>>) -> usize {
 fn count_part2(
     inputs: &HashMap<String, Vec<String>>,
     s: &String,
-    memo: &mut HashMap<String, (usize, usize, usize)>,
-) -> (usize, usize, usize) {
+    memo: &mut HashMap<String, (usize, usize, usize, usize)>,
+) -> (usize, usize, usize, usize) {
     // Return cached result if already computed to avoid recalculation
     if let Some(&cached) = memo.get(s) {
         return cached;
@@ -59,28 +82,32 @@ fn count_part2(
 
     // Base case: reaching "out" is 1 valid path with no special visitors yet
     if s == "out" {
-        return (1, 0, 0);
+        return (1, 0, 0, 0);
     }
 
     // Recursively sum path counts from all neighbors
-    let mut count = (0, 0, 0);
+    let mut count = (0, 0, 0, 0);
     if let Some(neighbors) = inputs.get(s) {
         for neighbor in neighbors {
-            let (c1, c2, c3) = count_part2(inputs, neighbor, memo);
+            let (c1, c2, c3, c4) = count_part2(inputs, neighbor, memo);
             count.0 += c1; // Accumulate all paths to "out"
             count.1 += c2; // Accumulate paths that visited "dac"
-            count.2 += c3; // Accumulate paths that visited both "dac" and "fft"
+            count.2 += c3; // Accumulate paths that visited "fft"
+            count.3 += c4; // Accumulate paths that visited both "dac" and "fft"
         }
     }
 
-    // Mark the "dac" node: all paths from here count as having visited "dac"
+    // Mark the "dac" node: all paths from here count as having visited "dac",
+    // and those that visited "fft" further on now qualify as visited both
     if s == "dac" {
         count.1 = count.0;
+        count.3 = count.2;
     }
 
-    // Mark the "fft" node: count.1 (paths that visited "dac") now qualify as visited both
+    // Mark the "fft" node: likewise, with "dac" further on
     if s == "fft" {
-        count.2 = count.1;
+        count.2 = count.0;
+        count.3 = count.1;
     }
 
     // Cache this node's result for future lookups
@@ -90,12 +117,75 @@ fn count_part2(
 
 #[aoc(day11, part2)]
 pub fn part2(inputs: &HashMap<String, Vec<String>>) -> usize {
-    count_part2(inputs, &"svr".into(), &mut Default::default()).2
+    count_part2(inputs, &"svr".into(), &mut Default::default()).3
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 11;
+    type Parsed = HashMap<String, Vec<String>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(inputs: &Self::Parsed) -> Answer {
+        part1(inputs).into()
+    }
+
+    fn part2(inputs: &Self::Parsed) -> Answer {
+        part2(inputs).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Walks every path, one at a time.
+
+    use std::collections::HashMap;
+
+    fn walk<'a>(
+        graph: &HashMap<&'a str, Vec<&'a str>>,
+        device: &'a str,
+        path: &mut Vec<&'a str>,
+        via: &[&str],
+    ) -> usize {
+        if device == "out" {
+            return usize::from(via.iter().all(|v| path.contains(v)));
+        }
+
+        path.push(device);
+        let paths = graph
+            .get(device)
+            .into_iter()
+            .flatten()
+            .map(|next| walk(graph, next, path, via))
+            .sum();
+        path.pop();
+        paths
+    }
+
+    /// The paths from `from` to `out` that pass through every device in `via`.
+    pub fn paths(input: &str, from: &str, via: &[&str]) -> usize {
+        let graph = input
+            .lines()
+            .map(|line| {
+                let (device, outputs) = line.split_once(": ").unwrap();
+                (device, outputs.split(' ').collect())
+            })
+            .collect();
+
+        walk(&graph, from, &mut Vec::new(), via)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::rng::Rng;
 
     const SAMPLE: &str = r"aaa: you hhh
 you: bbb ccc
@@ -129,6 +219,15 @@ hhh: out";
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let err = try_generator("aaa: you hhh\nyou bbb ccc").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+
+        let err = try_generator("aaa: you hhh\nyou: bbb  ccc").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 10));
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 5);
@@ -139,19 +238,42 @@ hhh: out";
         assert_eq!(part2(&generator(SAMPLE2)), 2);
     }
 
-    mod regression {
-        use super::*;
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::paths(SAMPLE, "you", &[]), 5);
+        assert_eq!(oracle::paths(SAMPLE2, "svr", &["dac", "fft"]), 2);
 
-        const INPUT: &str = include_str!("../input/2025/day11.txt");
-        const ANSWERS: (usize, usize) = (607, 506264456238938);
+        let synth = crate::synth::synth(11).unwrap();
+        let mut rng = Rng::new(11);
+        for seed in 0..50 {
+            let mut input = synth.input(seed, 30 + seed as usize * 4);
+            // The synthesized graphs pass fft first; half the time, dac
+            if rng.chance(0.5) {
+                input = input
+                    .replace("fft", "#")
+                    .replace("dac", "fft")
+                    .replace("#", "dac");
+            }
 
-        #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
+            let graph = generator(&input);
+            assert_eq!(part1(&graph), oracle::paths(&input, "you", &[]), "{input}");
+            assert_eq!(
+                part2(&graph),
+                oracle::paths(&input, "svr", &["dac", "fft"]),
+                "{input}"
+            );
+        }
+    }
 
-            assert_eq!(part1(&output), ANSWERS.0);
-            assert_eq!(part2(&output), ANSWERS.1);
+    proptest! {
+        #[test]
+        fn paths_at_most_all_test(seed in any::<u64>(), size in 1..200usize) {
+            // svr feeds you, so every path counted is part of some path from svr
+            let input = crate::synth::synth(11).unwrap().input(seed, size);
+            let graph = generator(&input);
+            let all = oracle::paths(&input, "svr", &[]);
+            prop_assert!(part1(&graph) <= all);
+            prop_assert!(part2(&graph) <= all);
         }
     }
 }
@@ -1,7 +1,12 @@
 use aoc_runner_derive::{aoc, aoc_generator};
-use nom::{IResult, Parser, bytes::complete::tag};
+use nom::{IResult, Parser, bytes::complete::tag, combinator::all_consuming};
 
-use crate::common::nom::nom_usize;
+use crate::{
+    common::nom::nom_usize,
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Solution},
+};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Region {
@@ -29,31 +34,62 @@ fn parse_region(line: &str) -> IResult<&str, (usize, usize, [usize; 6])> {
     Ok((line, (width, height, counts)))
 }
 
-#[aoc_generator(day12)]
-pub fn generator(input: &str) -> (Vec<Region>, [usize; 6]) {
+pub fn try_generator(input: &str) -> Result<(Vec<Region>, [usize; 6]), ParseError> {
     let mut iter = input.split("\n\n");
 
     let mut counts = [0; 6];
     for i in counts.iter_mut() {
-        let shape = iter.next().unwrap();
+        let shape = iter
+            .next()
+            .ok_or_else(|| ParseError::new(12, input, input.len(), "expected 6 present shapes"))?;
         *i = count_shape(shape);
     }
 
-    let regions = iter.next().unwrap();
-    (
-        regions
-            .lines()
-            .map(|line| {
-                let (width, height, shape_counts) = parse_region(line).unwrap().1;
-                Region {
-                    width,
-                    height,
-                    shape_counts,
-                }
+    let regions = iter
+        .next()
+        .ok_or_else(|| ParseError::new(12, input, input.len(), "expected a list of regions"))?;
+    if let Some(rest) = iter.next() {
+        return Err(ParseError::at(
+            12,
+            input,
+            rest,
+            "unexpected input after the regions",
+        ));
+    }
+
+    let regions = regions
+        .lines()
+        .map(|line| {
+            let (width, height, shape_counts) = all_consuming(parse_region)
+                .parse(line)
+                .map_err(|e| ParseError::from_nom(12, input, e))?
+                .1;
+
+            // part1 multiplies these out, so they must fit
+            let presents = shape_counts
+                .iter()
+                .zip(&counts)
+                .try_fold(0usize, |area, (n, size)| {
+                    area.checked_add(n.checked_mul(*size)?)
+                });
+            if width.checked_mul(height).is_none() || presents.is_none() {
+                return Err(ParseError::at(12, input, line, "region too large"));
+            }
+
+            Ok(Region {
+                width,
+                height,
+                shape_counts,
             })
-            .collect(),
-        counts,
-    )
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((regions, counts))
+}
+
+#[aoc_generator(day12)]
+pub fn generator(input: &str) -> (Vec<Region>, [usize; 6]) {
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 #[aoc(day12, part1)]
@@ -82,9 +118,178 @@ pub fn part1(inputs: &(Vec<Region>, [usize; 6])) -> usize {
         .count()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = 12;
+    type Parsed = (Vec<Region>, [usize; 6]);
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(inputs: &Self::Parsed) -> Answer {
+        part1(inputs).into()
+    }
+}
+
+#[cfg(test)]
+mod oracle {
+    //! Packs the presents for real: fills the first free cell of the region
+    //! with every orientation of every remaining present, or leaves it empty.
+
+    type Shape = Vec<(isize, isize)>;
+
+    /// The distinct rotations and flips of a shape, each as offsets from its
+    /// first cell in reading order.
+    fn orientations(shape: &str) -> Vec<Shape> {
+        let cells = shape
+            .lines()
+            .skip(1)
+            .enumerate()
+            .flat_map(|(r, line)| {
+                line.bytes()
+                    .enumerate()
+                    .filter(|&(_, b)| b == b'#')
+                    .map(move |(c, _)| (r as isize, c as isize))
+            })
+            .collect::<Vec<_>>();
+
+        let mut orientations = Vec::<Shape>::new();
+        for flip in [false, true] {
+            for turns in 0..4 {
+                let mut oriented = cells
+                    .iter()
+                    .map(|&(r, c)| {
+                        let (r, c) = if flip { (r, -c) } else { (r, c) };
+                        (0..turns).fold((r, c), |(r, c), _| (c, -r))
+                    })
+                    .collect::<Vec<_>>();
+                oriented.sort_unstable();
+                let first = oriented[0];
+                for cell in &mut oriented {
+                    *cell = (cell.0 - first.0, cell.1 - first.1);
+                }
+                if !orientations.contains(&oriented) {
+                    orientations.push(oriented);
+                }
+            }
+        }
+
+        orientations
+    }
+
+    struct Packer<'a> {
+        width: usize,
+        height: usize,
+        filled: Vec<bool>,
+        shapes: &'a [Vec<Shape>],
+    }
+
+    impl Packer<'_> {
+        /// The cells `shape` covers with its first cell at `at`, if they are
+        /// all in the region and free.
+        fn cells(&self, at: usize, shape: &Shape) -> Option<Vec<usize>> {
+            let (r, c) = (at / self.width, at % self.width);
+            shape
+                .iter()
+                .map(|&(dr, dc)| {
+                    let r = r.checked_add_signed(dr).filter(|&r| r < self.height)?;
+                    let c = c.checked_add_signed(dc).filter(|&c| c < self.width)?;
+                    Some(r * self.width + c).filter(|&cell| !self.filled[cell])
+                })
+                .collect()
+        }
+
+        /// Whether `counts` presents fit in the free cells from `at` on, leaving
+        /// at most `slack` of them empty.
+        fn pack(&mut self, at: usize, counts: &mut [usize], slack: usize) -> bool {
+            if counts.iter().all(|&count| count == 0) {
+                return true;
+            }
+            let Some(at) = (at..self.filled.len()).find(|&cell| !self.filled[cell]) else {
+                return false;
+            };
+
+            for i in 0..counts.len() {
+                if counts[i] == 0 {
+                    continue;
+                }
+                counts[i] -= 1;
+                for shape in &self.shapes[i] {
+                    let Some(cells) = self.cells(at, shape) else {
+                        continue;
+                    };
+
+                    cells.iter().for_each(|&cell| self.filled[cell] = true);
+                    let packed = self.pack(at + 1, counts, slack);
+                    cells.iter().for_each(|&cell| self.filled[cell] = false);
+                    if packed {
+                        return true;
+                    }
+                }
+                counts[i] += 1;
+            }
+
+            if slack > 0 {
+                self.filled[at] = true;
+                let packed = self.pack(at + 1, counts, slack - 1);
+                self.filled[at] = false;
+                if packed {
+                    return true;
+                }
+            }
+
+            false
+        }
+    }
+
+    /// Whether the presents fit in each region.
+    pub fn fits(input: &str) -> Vec<bool> {
+        let blocks = input.split("\n\n").collect::<Vec<_>>();
+        let (regions, shapes) = blocks.split_last().unwrap();
+        let shapes = shapes.iter().map(|s| orientations(s)).collect::<Vec<_>>();
+        let areas = shapes.iter().map(|s| s[0].len()).collect::<Vec<_>>();
+
+        regions
+            .lines()
+            .map(|line| {
+                let (size, counts) = line.split_once(": ").unwrap();
+                let (width, height) = size.split_once('x').unwrap();
+                let (width, height): (usize, usize) =
+                    (width.parse().unwrap(), height.parse().unwrap());
+                // Every rotation is tried, so the region can be turned to fill the
+                // shorter rows first, which dead-ends much sooner
+                let (width, height) = (width.min(height), width.max(height));
+                let mut counts = counts
+                    .split(' ')
+                    .map(|n| n.parse().unwrap())
+                    .collect::<Vec<usize>>();
+
+                let needed = counts.iter().zip(&areas).map(|(n, a)| n * a).sum::<usize>();
+                let Some(slack) = (width * height).checked_sub(needed) else {
+                    return false;
+                };
+
+                Packer {
+                    width,
+                    height,
+                    filled: vec![false; width * height],
+                    shapes: &shapes,
+                }
+                .pack(0, &mut counts, slack)
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
     use super::*;
+    use crate::rng::Rng;
 
     const SAMPLE: &str = r"0:
 ###
@@ -127,6 +332,26 @@ mod tests {
         // assert_eq!(generator(SAMPLE), Object());
     }
 
+    #[test]
+    pub fn try_generator_test() {
+        let shapes = "0:\n###\n\n1:\n###\n\n2:\n###\n\n3:\n###\n\n4:\n###\n\n5:\n###";
+        let err = try_generator(shapes).unwrap_err();
+        assert_eq!((err.line, err.column), (17, 4));
+
+        let err =
+            try_generator(&format!("{shapes}\n\n4x4: 0 0 0 0 2 0\n12x5: 1 0 1 0")).unwrap_err();
+        assert_eq!((err.line, err.column), (20, 14));
+
+        let err = try_generator(&format!("{shapes}\n\n4x4: 0 0 0 0 2 0 junk")).unwrap_err();
+        assert_eq!((err.line, err.column), (19, 17));
+        let err = try_generator(&format!("{shapes}\n\n4x4: 0 0 0 0 2 0\n\n6:")).unwrap_err();
+        assert_eq!((err.line, err.column), (21, 1));
+        let huge = format!("{shapes}\n\n99999999999x99999999999: 0 0 0 0 2 0");
+        assert_eq!(try_generator(&huge).unwrap_err().line, 19);
+        let err = try_generator(&format!("{shapes}\n\n4x4: {} 0 0 0 0 0", usize::MAX)).unwrap_err();
+        assert_eq!((err.line, err.column), (19, 1));
+    }
+
     #[test]
     pub fn part1_test() {
         assert_eq!(part1(&generator(SAMPLE)), 2);
@@ -137,19 +362,58 @@ mod tests {
         // assert_eq!(part2(&generator(SAMPLE)), 336);
     }
 
-    mod regression {
-        use super::*;
+    #[test]
+    pub fn oracle_test() {
+        assert_eq!(oracle::fits(SAMPLE), [true, true, false]);
 
-        const INPUT: &str = include_str!("../input/2025/day12.txt");
-        const ANSWERS: (usize, usize) = (569, 0);
+        // The fast check is a heuristic, so it's only held to regions that are
+        // clearly decided: too small for the presents' area, or roomy enough
+        // for every present to get a 3x3 block of its own
+        let synth = crate::synth::synth(12).unwrap();
+        for seed in 0..50 {
+            let mut rng = Rng::new(seed);
+            let shapes = synth.input(seed, 0);
+            let areas = generator(&format!("{shapes}1x1: 0 0 0 0 0 0")).1;
 
-        #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            let output = generator(input);
+            let (width, height) = (rng.range(3..=9) as usize, rng.range(3..=9) as usize);
+            let mut counts = [0; 6];
+            if rng.chance(0.5) {
+                for _ in 0..rng.range(0..=(width as u64 / 3) * (height as u64 / 3)) {
+                    counts[rng.index(6)] += 1;
+                }
+            } else {
+                while counts.iter().zip(&areas).map(|(n, a)| n * a).sum::<usize>() <= width * height
+                {
+                    counts[rng.index(6)] += 1;
+                }
+            }
+            let input = format!("{shapes}{width}x{height}: {}", counts.iter().join(" "));
 
-            assert_eq!(part1(&output), ANSWERS.0);
-            // assert_eq!(part2(&output), ANSWERS.1);
+            assert_eq!(
+                part1(&generator(&input)),
+                oracle::fits(&input)
+                    .into_iter()
+                    .filter(|&fits| fits)
+                    .count(),
+                "{input}"
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn fits_only_with_room_test(seed in any::<u64>(), size in 1..50usize) {
+            // No region is counted unless it has at least its presents' area
+            let (regions, areas) = generator(&crate::synth::synth(12).unwrap().input(seed, size));
+            let roomy = regions
+                .iter()
+                .filter(|region| {
+                    let needed: usize =
+                        region.shape_counts.iter().zip(&areas).map(|(n, a)| n * a).sum();
+                    needed <= region.width * region.height
+                })
+                .count();
+            prop_assert!(part1(&(regions, areas)) <= roomy);
         }
     }
 }
@@ -0,0 +1,151 @@
+use std::fmt;
+
+use nom::error::ErrorKind;
+
+/// A malformed puzzle input, located by line and column.
+///
+/// `line` and `column` are 1-based, and `snippet` is the full text of the
+/// offending line so the error can be reported without the original input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error pointing at byte `offset` of `input`.
+    pub fn new(day: u8, input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            day,
+            line: input[..line_start].bytes().filter(|&b| b == b'\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end].to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error pointing at the start of `fragment`, which must be a
+    /// subslice of `input`. Anything else is reported at the end of the input.
+    pub fn at(day: u8, input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = (fragment.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset + fragment.len() <= input.len())
+            .unwrap_or(input.len());
+
+        Self::new(day, input, offset, message)
+    }
+
+    /// Converts a nom error produced while parsing `input` (or a subslice of it).
+    pub fn from_nom(day: u8, input: &str, err: nom::Err<nom::error::Error<&str>>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => {
+                Self::new(day, input, input.len(), "unexpected end of input")
+            }
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let (fragment, message) = match e.code {
+                    // A line that failed to parse is left over along with the
+                    // line ending before it, so point at the line itself
+                    ErrorKind::Eof => (
+                        e.input.trim_start_matches(['\r', '\n']),
+                        "unexpected trailing input".to_string(),
+                    ),
+                    code => (
+                        e.input,
+                        format!("unexpected input (expected {})", code.description()),
+                    ),
+                };
+                Self::at(day, input, fragment, message)
+            }
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} line {} column {}: {}: {:?}",
+            self.day, self.line, self.column, self.message, self.snippet
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why [`crate::solve`] could not produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    UnknownDay(u8),
+    Parse(ParseError),
+}
+
+impl From<ParseError> for SolveError {
+    fn from(e: ParseError) -> Self {
+        SolveError::Parse(e)
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::UnknownDay(day) => write!(f, "day {day} is not implemented"),
+            SolveError::Parse(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use nom::Parser;
+
+    use super::*;
+
+    #[test]
+    pub fn location_test() {
+        let input = "abc\ndef\nghi";
+        let err = ParseError::at(1, input, &input[5..], "bad");
+
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.snippet, "def");
+        assert_eq!(err.to_string(), r#"day 1 line 2 column 2: bad: "def""#);
+    }
+
+    #[test]
+    pub fn trailing_input_test() {
+        let input = "1\n2\nx";
+        let err = nom::combinator::all_consuming(nom::multi::separated_list1(
+            nom::character::complete::line_ending::<_, nom::error::Error<_>>,
+            nom::character::complete::u8,
+        ))
+        .parse(input)
+        .map_err(|e| ParseError::from_nom(1, input, e))
+        .unwrap_err();
+
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.snippet, "x");
+    }
+
+    #[test]
+    pub fn end_of_input_test() {
+        let input = "abc\n";
+        let err = ParseError::new(1, input, input.len(), "eof");
+
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.snippet, "");
+    }
+}
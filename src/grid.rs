@@ -0,0 +1,228 @@
+//! A rectangular grid of cells, for puzzles drawn as text.
+//!
+//! Positions are `(row, column)`, counted from the top left.
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use crate::error::ParseError;
+
+const NEIGHBORS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const NEIGHBORS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// # Panics
+    ///
+    /// If `cells` does not hold exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells"
+        );
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses one cell per byte and one row per line. `cell` turns a byte into
+    /// a cell, or `None` if the byte isn't allowed, which is reported as `expected`.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(u8) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let width = input.lines().next().map_or(0, str::len);
+        let mut cells = Vec::with_capacity(input.len());
+        let mut height = 0;
+
+        for line in input.lines() {
+            if line.len() != width {
+                return Err(ParseError::at(
+                    day,
+                    input,
+                    line,
+                    format!("expected {width} columns"),
+                ));
+            }
+
+            for (pos, b) in line.bytes().enumerate() {
+                cells.push(
+                    cell(b).ok_or_else(|| ParseError::at(day, input, &line[pos..], expected))?,
+                );
+            }
+            height += 1;
+        }
+
+        Ok(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (r, c): (usize, usize)) -> Option<&T> {
+        (r < self.height && c < self.width).then(|| &self.cells[r * self.width + c])
+    }
+
+    pub fn get_mut(&mut self, (r, c): (usize, usize)) -> Option<&mut T> {
+        (r < self.height && c < self.width).then(|| &mut self.cells[r * self.width + c])
+    }
+
+    pub fn row(&self, r: usize) -> &[T] {
+        &self.cells[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|r| self.row(r))
+    }
+
+    pub fn col(&self, c: usize) -> impl Iterator<Item = &T> {
+        assert!(c < self.width, "column {c} out of bounds");
+        self.cells[c..].iter().step_by(self.width)
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |r| (0..width).map(move |c| (r, c)))
+    }
+
+    /// The first position, row by row, whose cell matches `predicate`.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let i = self.cells.iter().position(predicate)?;
+        Some((i / self.width, i % self.width))
+    }
+
+    /// The positions up, left, right and down of `pos` that are in the grid.
+    pub fn neighbors4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        self.offsets(pos, &NEIGHBORS4)
+    }
+
+    /// The positions around `pos`, diagonals included, that are in the grid.
+    pub fn neighbors8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        self.offsets(pos, &NEIGHBORS8)
+    }
+
+    fn offsets(
+        &self,
+        (r, c): (usize, usize),
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let (height, width) = (self.height, self.width);
+        offsets.iter().filter_map(move |&(dr, dc)| {
+            let r = r.checked_add_signed(dr).filter(|&r| r < height)?;
+            let c = c.checked_add_signed(dc).filter(|&c| c < width)?;
+            Some((r, c))
+        })
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos:?} out of bounds"))
+    }
+}
+
+/// Renders the grid as text again, one line per row.
+impl<T: Copy + Into<char>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (r, row) in self.rows().enumerate() {
+            if r > 0 {
+                f.write_str("\n")?;
+            }
+            for &cell in row {
+                fmt::Write::write_char(f, cell.into())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(0, input, "expected '#' or '.'", |b| {
+            matches!(b, b'#' | b'.').then_some(b)
+        })
+    }
+
+    #[test]
+    pub fn parse_test() {
+        let grid = parse("#..\n.#.").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 1)], b'#');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.row(1), b".#.");
+        assert_eq!(grid.col(0).copied().collect::<Vec<_>>(), b"#.");
+        assert_eq!(grid.to_string(), "#..\n.#.");
+
+        let err = parse("#..\n.#").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        let err = parse("#..\n.x.").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    pub fn neighbors_test() {
+        let grid = Grid::filled(3, 2, '.');
+
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 5);
+        assert_eq!(grid.positions().count(), 6);
+        assert_eq!(grid.position(|&c| c == '#'), None);
+    }
+}
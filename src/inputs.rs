@@ -0,0 +1,152 @@
+//! Locating puzzle inputs at runtime, so nothing fails to compile when the
+//! private `input` submodule is not checked out.
+
+use std::{
+    borrow::Cow,
+    env, io,
+    path::{Path, PathBuf},
+};
+
+/// Default input directory, relative to the crate root.
+pub const INPUT_DIR: &str = "input/2025";
+
+/// The input directory, overridden by the `AOC_INPUT_DIR` environment variable.
+pub fn input_dir() -> PathBuf {
+    env::var_os("AOC_INPUT_DIR").map_or_else(|| PathBuf::from(INPUT_DIR), PathBuf::from)
+}
+
+/// The profile whose inputs sit directly in the input directory.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The directory holding a profile's inputs: the input directory itself for
+/// [`DEFAULT_PROFILE`], otherwise a subdirectory named after the profile.
+pub fn profile_dir(dir: &Path, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        dir.to_path_buf()
+    } else {
+        dir.join(profile)
+    }
+}
+
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day}.txt"))
+}
+
+/// Puts an input in the canonical form every generator expects: no UTF-8
+/// BOM, `\n` line endings, no whitespace at the end of any line, and no
+/// trailing blank lines or newline.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input).trim_end();
+
+    if input
+        .split('\n')
+        .all(|line| line.len() == line.trim_end().len())
+    {
+        Cow::Borrowed(input)
+    } else {
+        Cow::Owned(
+            input
+                .split('\n')
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+/// Reads a puzzle input, [normalized](normalize).
+pub fn read_input(path: &Path) -> io::Result<String> {
+    let input = std::fs::read_to_string(path)?;
+
+    Ok(normalize(&input).into_owned())
+}
+
+/// FNV-1a of an input, to tell inputs apart in reports without publishing them.
+pub fn hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The days that have an input file in `dir`, in order.
+pub fn days(dir: &Path) -> io::Result<Vec<u8>> {
+    let mut days = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let name = entry?.file_name();
+        let day: Option<u8> = name
+            .to_str()
+            .and_then(|name| name.strip_prefix("day")?.strip_suffix(".txt")?.parse().ok());
+
+        days.extend(day);
+    }
+
+    days.sort_unstable();
+    Ok(days)
+}
+
+/// Every profile in `dir` that has at least one input, [`DEFAULT_PROFILE`] first.
+pub fn profiles(dir: &Path) -> io::Result<Vec<String>> {
+    let mut profiles = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir()
+            && let Some(name) = entry.file_name().to_str()
+            && !days(&entry.path())?.is_empty()
+        {
+            profiles.push(name.to_string());
+        }
+    }
+
+    profiles.sort_unstable();
+    if !days(dir)?.is_empty() {
+        profiles.insert(0, DEFAULT_PROFILE.to_string());
+    }
+
+    Ok(profiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn profiles_test() {
+        let dir = env::temp_dir().join(format!("aoc-profiles-{}", std::process::id()));
+        for path in [
+            "day1.txt",
+            "day12.txt",
+            "notes.txt",
+            "bob/day3.txt",
+            "alice/day1.txt",
+        ] {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        std::fs::create_dir_all(dir.join("empty")).unwrap();
+
+        assert_eq!(profiles(&dir).unwrap(), ["default", "alice", "bob"]);
+        assert_eq!(days(&dir).unwrap(), [1, 12]);
+        assert_eq!(days(&profile_dir(&dir, "bob")).unwrap(), [3]);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    pub fn normalize_test() {
+        assert_eq!(normalize("L68\nL30"), "L68\nL30");
+        assert_eq!(normalize("\u{feff}L68\r\nL30\r\n\r\n"), "L68\nL30");
+        assert_eq!(normalize("1-2\n\n3\n  \n\t\n"), "1-2\n\n3");
+        assert_eq!(normalize(" 45 \n*   +  \n"), " 45\n*   +");
+        assert_eq!(normalize("L68 \nL30\t\r\n"), "L68\nL30");
+        assert_eq!(normalize("\n\n"), "");
+        assert!(matches!(normalize("L68\n"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    pub fn hash_test() {
+        assert_eq!(hash(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash("a"), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(hash("L68\nL30"), hash("L68\nL31"));
+    }
+}
@@ -0,0 +1,258 @@
+//! Sets of integers stored as sorted, disjoint, non-adjacent inclusive
+//! intervals, so huge ranges cost no more than small ones.
+
+use std::{fmt, ops::RangeInclusive};
+
+/// An integer type an [`IntervalSet`] can hold.
+pub trait Point: Copy + Ord + fmt::Debug {
+    fn succ(self) -> Option<Self>;
+    fn pred(self) -> Option<Self>;
+
+    /// The number of points in `lo..=hi`, which must not be empty. A `u128`,
+    /// so even a full `u64` range fits.
+    fn count(lo: Self, hi: Self) -> u128;
+}
+
+macro_rules! impl_point {
+    ($($t:ty),+) => {
+        $(
+            impl Point for $t {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn count(lo: Self, hi: Self) -> u128 {
+                    (hi as i128 - lo as i128) as u128 + 1
+                }
+            }
+        )+
+    };
+}
+
+impl_point!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    /// `(start, end)`, inclusive, sorted, with a gap between neighbours.
+    intervals: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Point> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The index of the first interval ending at or after `x`, or just before it.
+    fn touching_from(&self, x: T) -> usize {
+        self.intervals
+            .partition_point(|&(_, end)| end.succ().is_some_and(|next| next < x))
+    }
+
+    /// The index after the last interval starting at or before `x`, or just after it.
+    fn touching_to(&self, x: T) -> usize {
+        self.intervals
+            .partition_point(|&(start, _)| start.pred().is_none_or(|prev| prev <= x))
+    }
+
+    /// Adds every point in `range`, merging with any overlapping or adjacent intervals.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        let (i, j) = (self.touching_from(start), self.touching_to(end));
+        if i < j {
+            start = start.min(self.intervals[i].0);
+            end = end.max(self.intervals[j - 1].1);
+        }
+        self.intervals.splice(i..j, [(start, end)]);
+    }
+
+    /// Removes every point in `range`, splitting any interval it falls inside.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        let i = self.intervals.partition_point(|&(_, e)| e < start);
+        let j = self.intervals.partition_point(|&(s, _)| s <= end);
+        if i >= j {
+            return;
+        }
+
+        let (first, last) = (self.intervals[i].0, self.intervals[j - 1].1);
+        let left = start
+            .pred()
+            .filter(|_| first < start)
+            .map(|prev| (first, prev));
+        let right = end.succ().filter(|_| last > end).map(|next| (next, last));
+        self.intervals.splice(i..j, left.into_iter().chain(right));
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let i = self.intervals.partition_point(|&(_, end)| end < x);
+        self.intervals.get(i).is_some_and(|&(start, _)| start <= x)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of points in the set.
+    pub fn len(&self) -> u128 {
+        self.intervals
+            .iter()
+            .map(|&(start, end)| T::count(start, end))
+            .sum()
+    }
+
+    /// The intervals, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+
+        while i < a.len() && j < b.len() {
+            let start = a[i].0.max(b[j].0);
+            let end = a[i].1.min(b[j].1);
+            if start <= end {
+                intervals.push((start, end));
+            }
+
+            if a[i].1 < b[j].1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// Every point in `within` that is not in the set.
+    pub fn complement(&self, within: RangeInclusive<T>) -> Self {
+        let mut complement = IntervalSet::new();
+        complement.insert(within);
+        complement.difference(self)
+    }
+}
+
+impl<T: Point> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Point> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(ranges);
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    pub fn insert_test() {
+        let mut set: IntervalSet<u64> = [10..=14, 3..=5, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3..=5, 10..=20]);
+
+        // Adjacent intervals merge too
+        set.insert(6..=9);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3..=20]);
+        assert_eq!(set.len(), 18);
+        assert!(set.contains(3) && set.contains(20) && !set.contains(21));
+    }
+
+    #[test]
+    pub fn remove_test() {
+        let mut set: IntervalSet<u8> = [0..=255].into_iter().collect();
+        set.remove(10..=19);
+        set.remove(0..=0);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [1..=9, 20..=255]);
+        assert_eq!(set.len(), 245);
+        assert_eq!(set.complement(5..=30).iter().collect::<Vec<_>>(), [10..=19]);
+    }
+
+    fn naive(set: &IntervalSet<u8>) -> Vec<bool> {
+        (0..=255).map(|x| set.contains(x)).collect()
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<RangeInclusive<u8>>> {
+        prop::collection::vec((any::<u8>(), 0..40u8), 0..8).prop_map(|ranges| {
+            ranges
+                .into_iter()
+                .map(|(start, len)| start..=start.saturating_add(len))
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn naive_test(a in ranges(), b in ranges(), removed in ranges()) {
+            let mut set: IntervalSet<u8> = a.iter().cloned().collect();
+            let mut points = [false; 256];
+            for range in &a {
+                range.clone().for_each(|x| points[usize::from(x)] = true);
+            }
+            for range in &removed {
+                set.remove(range.clone());
+                range.clone().for_each(|x| points[usize::from(x)] = false);
+            }
+            let other: IntervalSet<u8> = b.iter().cloned().collect();
+
+            // Sorted, disjoint and not adjacent
+            prop_assert!(set.intervals.windows(2).all(|w| w[0].1.succ().unwrap() < w[1].0));
+            prop_assert_eq!(naive(&set), points);
+            prop_assert_eq!(set.len(), points.iter().filter(|&&p| p).count() as u128);
+
+            let (x, y) = (naive(&set), naive(&other));
+            let zip = |f: fn(bool, bool) -> bool| {
+                x.iter().zip(&y).map(|(&x, &y)| f(x, y)).collect::<Vec<_>>()
+            };
+            prop_assert_eq!(naive(&set.union(&other)), zip(|x, y| x || y));
+            prop_assert_eq!(naive(&set.intersection(&other)), zip(|x, y| x && y));
+            prop_assert_eq!(naive(&set.difference(&other)), zip(|x, y| x && !y));
+            prop_assert_eq!(naive(&set.complement(0..=255)), zip(|x, _| !x));
+        }
+    }
+}
@@ -1,5 +1,23 @@
 use aoc_runner_derive::aoc_lib;
+pub mod alloc_stats;
+pub mod answers;
+pub mod baseline;
 pub mod common;
+pub mod error;
+pub mod grid;
+pub mod inputs;
+pub mod interval_set;
+#[cfg(test)]
+mod regression;
+pub mod rng;
+pub mod solution;
+#[cfg(any(test, feature = "synth"))]
+pub mod synth;
+pub mod trace;
+pub mod union_find;
+
+use error::SolveError;
+use solution::{Answer, DynSolution, Part};
 
 pub mod day01;
 pub mod day02;
@@ -14,4 +32,34 @@ pub mod day10;
 pub mod day11;
 pub mod day12;
 // Insert before
+
+/// Every implemented day, in order.
+pub static SOLUTIONS: &[&dyn DynSolution] = &[
+    &day01::Solver,
+    &day02::Solver,
+    &day03::Solver,
+    &day04::Solver,
+    &day05::Solver,
+    &day06::Solver,
+    &day07::Solver,
+    &day08::Solver,
+    &day09::Solver,
+    &day10::Solver,
+    &day11::Solver,
+    &day12::Solver,
+    // Insert solution before
+];
+
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+/// Parses `input` and solves one part of `day`.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<Answer, SolveError> {
+    let solution = solution(day).ok_or(SolveError::UnknownDay(day))?;
+    let parsed = solution.parse(input)?;
+
+    Ok(solution.solve(&parsed, part))
+}
+
 aoc_lib! { year = 2025 }
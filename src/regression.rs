@@ -0,0 +1,68 @@
+//! Runs every input found in the input directory, for every profile, and
+//! checks those named in the answers manifest against their known answers.
+
+use std::{collections::BTreeSet, io::Write};
+
+use crate::{
+    answers::Answers,
+    inputs::{days, input_dir, input_path, profile_dir, profiles, read_input},
+    solution::Part,
+};
+
+#[test]
+pub fn test() {
+    let dir = input_dir();
+    let answers = Answers::load_all(&dir).unwrap();
+    let mut failures = Vec::new();
+
+    let mut cases: BTreeSet<(String, u8)> = answers
+        .iter()
+        .map(|(profile, day, _)| (profile.to_string(), day))
+        .collect();
+    for profile in profiles(&dir).unwrap_or_default() {
+        for day in days(&profile_dir(&dir, &profile)).unwrap() {
+            cases.insert((profile.clone(), day));
+        }
+    }
+
+    for (profile, day) in cases {
+        let path = input_path(&profile_dir(&dir, &profile), day);
+        let input = match read_input(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                // Written directly to stderr, since the test harness captures eprintln!
+                let _ = writeln!(
+                    std::io::stderr(),
+                    "skipping {profile} day{day}: {}: {e}",
+                    path.display()
+                );
+                continue;
+            }
+        };
+
+        let Some(solution) = crate::solution(day) else {
+            failures.push(format!("{profile} day{day}: day is not implemented"));
+            continue;
+        };
+        let parsed = match solution.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                failures.push(format!("{profile} day{day}: {e}"));
+                continue;
+            }
+        };
+
+        for part in Part::ALL {
+            let answer = solution.solve(&parsed, part).to_string();
+            if let Some(expected) = answers.get(&profile, day, part)
+                && answer != expected
+            {
+                failures.push(format!(
+                    "{profile} day{day} part {part}: expected {expected}, got {answer}"
+                ));
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,208 @@
+use std::{any::Any, fmt, str::FromStr};
+
+use crate::{error::ParseError, inputs::normalize};
+
+/// A puzzle answer, independent of the integer type each day computes it in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Text(String),
+    /// The part has no answer, e.g. day 12 part 2.
+    Unsolved,
+}
+
+impl Answer {
+    /// The variant's name, for machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Unsigned(_) => "unsigned",
+            Answer::Signed(_) => "signed",
+            Answer::Text(_) => "text",
+            Answer::Unsolved => "unsolved",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => n.fmt(f),
+            Answer::Signed(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+            Answer::Unsolved => f.pad("unsolved"),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident as $target:ty: $($t:ty),+) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )+
+    };
+}
+
+impl_from!(Unsigned as u64: u64, usize, u32, u16);
+impl_from!(Signed as i64: i64, isize, i32, i16);
+
+/// Too big for a `u64` only on extreme inputs, so shown as text then.
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        u64::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Unsigned)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One = 1,
+    Two = 2,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        match n {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(n),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<u8>()
+            .ok()
+            .and_then(|n| Part::try_from(n).ok())
+            .ok_or_else(|| format!("invalid part: {s}"))
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (*self as u8).fmt(f)
+    }
+}
+
+/// A day's puzzle: a generator producing `Parsed`, and the two parts solved from it.
+pub trait Solution {
+    const DAY: u8;
+    type Parsed: Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(parsed: &Self::Parsed) -> Answer;
+
+    fn part2(_parsed: &Self::Parsed) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+/// The output of [`DynSolution::parse`], only usable with the solution that produced it.
+pub struct Parsed(Box<dyn Any + Send + Sync>);
+
+/// Object-safe version of [`Solution`], so days with different `Parsed` types
+/// can live in one registry.
+pub trait DynSolution: Send + Sync {
+    fn day(&self) -> u8;
+
+    /// Parses `input` after [normalizing](normalize) it.
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError>;
+
+    /// # Panics
+    ///
+    /// If `parsed` was produced by a different day.
+    fn solve(&self, parsed: &Parsed, part: Part) -> Answer;
+}
+
+impl<S: Solution + Send + Sync> DynSolution for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        Ok(Parsed(Box::new(S::parse(&normalize(input))?)))
+    }
+
+    fn solve(&self, parsed: &Parsed, part: Part) -> Answer {
+        let parsed = parsed
+            .0
+            .downcast_ref()
+            .unwrap_or_else(|| panic!("parsed input does not belong to day {}", S::DAY));
+
+        match part {
+            Part::One => S::part1(parsed),
+            Part::Two => S::part2(parsed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SOLUTIONS, error::SolveError, solve};
+
+    #[test]
+    pub fn answer_test() {
+        assert_eq!(
+            Answer::from(506264456238938usize).to_string(),
+            "506264456238938"
+        );
+        assert_eq!(Answer::from(-3i32), Answer::Signed(-3));
+        assert_eq!(Answer::from(7u128), Answer::Unsigned(7));
+        assert_eq!(Answer::from(u128::MAX).kind(), "text");
+        assert_eq!(format!("{:>9}", Answer::Unsolved), " unsolved");
+        assert_eq!(Answer::from(-3i32).kind(), "signed");
+    }
+
+    #[test]
+    pub fn registry_test() {
+        let days: Vec<_> = SOLUTIONS.iter().map(|s| s.day()).collect();
+        assert!(days.is_sorted_by(|a, b| a < b));
+        assert!(days.starts_with(&(1..=12).collect::<Vec<_>>()));
+    }
+
+    #[test]
+    pub fn solve_test() {
+        assert_eq!(
+            solve(1, Part::Two, "L68\nL30\nR48"),
+            Ok(Answer::Unsigned(2))
+        );
+        assert_eq!(
+            solve(12, Part::Two, ""),
+            Err(SolveError::Parse(
+                crate::day12::try_generator("").unwrap_err()
+            ))
+        );
+        assert_eq!(solve(26, Part::One, ""), Err(SolveError::UnknownDay(26)));
+
+        // Edited on Windows
+        assert_eq!(
+            solve(1, Part::Two, "\u{feff}L68\r\nL30\r\nR48\r\n\r\n"),
+            Ok(Answer::Unsigned(2))
+        );
+    }
+}
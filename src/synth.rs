@@ -0,0 +1,373 @@
+//! Synthesized puzzle inputs, for benchmarking without the private inputs.
+//!
+//! Every generator is deterministic in its seed and writes roughly `size`
+//! records (rotations, banks, machines, ...) in the day's input format.
+//!
+//! Only built for tests, or with the `synth` feature.
+
+use itertools::Itertools;
+
+use crate::rng::Rng;
+
+pub struct Synth {
+    pub day: u8,
+    /// The size of a real input.
+    pub typical_size: usize,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Synth {
+    const fn new(day: u8, typical_size: usize, generate: fn(&mut Rng, usize) -> String) -> Self {
+        Synth {
+            day,
+            typical_size,
+            generate,
+        }
+    }
+
+    pub fn input(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+}
+
+pub static SYNTHS: &[Synth] = &[
+    Synth::new(1, 4000, day01),
+    Synth::new(2, 35, day02),
+    Synth::new(3, 200, day03),
+    Synth::new(4, 140, day04),
+    Synth::new(5, 180, day05),
+    Synth::new(6, 1000, day06),
+    Synth::new(7, 141, day07),
+    Synth::new(8, 1000, day08),
+    Synth::new(9, 500, day09),
+    Synth::new(10, 160, day10),
+    Synth::new(11, 600, day11),
+    Synth::new(12, 1000, day12),
+];
+
+pub fn synth(day: u8) -> Option<&'static Synth> {
+    SYNTHS.iter().find(|s| s.day == day)
+}
+
+/// `size` rotations like `L68`.
+fn day01(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}{}", rng.pick(&['L', 'R']), rng.range(1..=999)))
+        .join("\n")
+}
+
+/// `size` comma-separated ID ranges of up to 10 digits.
+fn day02(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let digits = rng.range(1..=10) as u32;
+            let start = rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+            let span = rng.range(0..=start / 10 + 10);
+            format!("{start}-{}", (start + span).min(9_999_999_999))
+        })
+        .join(",")
+}
+
+/// `size` banks of 100 batteries.
+fn day03(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..100)
+                .map(|_| char::from(b'0' + rng.range(1..=9) as u8))
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+/// A `size`x`size` grid of paper rolls.
+fn day04(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| if rng.chance(0.6) { '@' } else { '.' })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+/// `size` fresh ID ranges, then five times as many available IDs.
+fn day05(rng: &mut Rng, size: usize) -> String {
+    const MAX: u64 = 560_000_000_000_000;
+
+    let ranges = (0..size)
+        .map(|_| {
+            let start = rng.range(1..=MAX);
+            format!("{start}-{}", start + rng.range(0..=MAX / 50))
+        })
+        .join("\n");
+    let ids = (0..size * 5).map(|_| rng.range(1..=MAX)).join("\n");
+
+    format!("{ranges}\n\n{ids}")
+}
+
+/// A worksheet of `size` problems with four numbers each.
+fn day06(rng: &mut Rng, size: usize) -> String {
+    const ROWS: usize = 4;
+    let mut lines = vec![Vec::new(); ROWS + 1];
+
+    for col in 0..size {
+        let op = *rng.pick(b"+*");
+        // Keep products small enough that the totals fit in a u64, and the
+        // last operator followed by padding
+        let max_width = if op == b'*' { 3 } else { 4 };
+        let min_width = if col + 1 == size { 2 } else { 1 };
+        let width = rng.range(min_width..=max_width) as usize;
+        let widest = rng.index(ROWS);
+
+        for (row, line) in lines.iter_mut().take(ROWS).enumerate() {
+            let digits = if row == widest {
+                width
+            } else {
+                1 + rng.index(width)
+            };
+            let number = (0..digits)
+                .map(|_| char::from(b'0' + rng.range(1..=9) as u8))
+                .collect::<String>();
+            line.push(if rng.chance(0.5) {
+                format!("{number:<width$}")
+            } else {
+                format!("{number:>width$}")
+            });
+        }
+        lines[ROWS].push(format!("{:<width$}", char::from(op)));
+    }
+
+    lines.iter().map(|cells| cells.join(" ")).join("\n")
+}
+
+/// A manifold `size` columns wide, with splitters on every other row.
+fn day07(rng: &mut Rng, size: usize) -> String {
+    let width = size | 1;
+    let mid = width / 2;
+    let height = width + 1;
+
+    // Each row of splitters multiplies the timelines by about (1 + p), so
+    // keep the total well inside a usize
+    let p = (2f64.powf(50.0 / (height / 2) as f64) - 1.0).min(0.5);
+
+    (0..height)
+        .map(|row| {
+            (0..width)
+                .map(|col| {
+                    let (k, offset) = (row / 2, col.abs_diff(mid));
+                    if row == 0 && col == mid {
+                        'S'
+                    } else if row > 0
+                        && row % 2 == 0
+                        && offset < k.min(mid)
+                        && offset % 2 == (k - 1) % 2
+                        && rng.chance(p)
+                    {
+                        '^'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+/// `size` junction boxes.
+fn day08(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| (0..3).map(|_| rng.range(0..=99_999)).join(","))
+        .join("\n")
+}
+
+/// About `size` red tiles forming a rectilinear polygon: a histogram of
+/// bars with distinct neighbouring heights.
+fn day09(rng: &mut Rng, size: usize) -> String {
+    let bars = (size / 2).max(2) - 1;
+    let mut x = rng.range(1..=1000);
+    let mut height = 0;
+    let mut tiles = Vec::with_capacity(2 * bars + 2);
+
+    tiles.push([x, 0]);
+    for _ in 0..bars {
+        let next = loop {
+            let h = rng.range(1..=99_999);
+            if h != height {
+                break h;
+            }
+        };
+        height = next;
+        tiles.push([x, height]);
+        x += rng.range(1..=200);
+        tiles.push([x, height]);
+    }
+    tiles.push([x, 0]);
+
+    tiles.iter().map(|[x, y]| format!("{x},{y}")).join("\n")
+}
+
+/// `size` machines, each solvable by construction.
+fn day10(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let lights = rng.range(3..=10) as usize;
+            let buttons = (0..rng.range(3..=13))
+                .map(|_| {
+                    let mut wiring = (0..lights).collect::<Vec<_>>();
+                    rng.shuffle(&mut wiring);
+                    wiring.truncate(1 + rng.index(lights / 2 + 1));
+                    wiring.sort_unstable();
+                    wiring
+                })
+                .collect::<Vec<_>>();
+
+            let mut indicator = vec!['.'; lights];
+            let mut joltages = vec![0; lights];
+            for wiring in &buttons {
+                let presses = rng.range(0..=20);
+                for &light in wiring {
+                    if presses % 2 == 1 {
+                        indicator[light] = if indicator[light] == '#' { '.' } else { '#' };
+                    }
+                    joltages[light] += presses;
+                }
+            }
+
+            format!(
+                "[{}] {} {{{}}}",
+                indicator.iter().collect::<String>(),
+                buttons
+                    .iter()
+                    .map(|wiring| format!("({})", wiring.iter().join(",")))
+                    .join(" "),
+                joltages.iter().join(",")
+            )
+        })
+        .join("\n")
+}
+
+/// A layered DAG of about `size` devices from `svr` through `fft` and then
+/// `dac` to `out`, with `you` near the top.
+fn day11(rng: &mut Rng, size: usize) -> String {
+    const RESERVED: [&str; 5] = ["svr", "you", "fft", "dac", "out"];
+
+    // Every device has one or two outputs in the next layer, so there are
+    // at most 2^layers paths
+    let layers = (size / 20).clamp(6, 40);
+    let per_layer = (size / layers).max(1);
+
+    let mut names = std::collections::HashSet::new();
+    let mut fresh = |rng: &mut Rng| loop {
+        let name = (0..3)
+            .map(|_| char::from(b'a' + rng.below(26) as u8))
+            .collect::<String>();
+        if !RESERVED.contains(&name.as_str()) && names.insert(name.clone()) {
+            break name;
+        }
+    };
+
+    let mut graph = (0..layers)
+        .map(|_| (0..per_layer).map(|_| fresh(rng)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    graph[0][0] = "svr".to_string();
+    graph[1][0] = "you".to_string();
+    graph[layers / 3][0] = "fft".to_string();
+    graph[2 * layers / 3][0] = "dac".to_string();
+    graph.push(vec!["out".to_string()]);
+
+    let mut lines = Vec::new();
+    for (layer, devices) in graph.iter().enumerate().take(layers) {
+        let next = &graph[layer + 1];
+        for (i, device) in devices.iter().enumerate() {
+            let mut outputs = (0..next.len()).collect::<Vec<_>>();
+            rng.shuffle(&mut outputs);
+            outputs.truncate(rng.range(1..=2) as usize);
+            // Chain the first device of every layer, so svr reaches out through fft and dac
+            if i == 0 && !outputs.contains(&0) {
+                outputs[0] = 0;
+            }
+            lines.push(format!(
+                "{device}: {}",
+                outputs.iter().map(|&i| &next[i]).join(" ")
+            ));
+        }
+    }
+
+    rng.shuffle(&mut lines);
+    lines.join("\n")
+}
+
+/// Six present shapes and `size` regions, some far too small for their presents.
+fn day12(rng: &mut Rng, size: usize) -> String {
+    let mut areas = [0; 6];
+    let shapes = areas
+        .iter_mut()
+        .enumerate()
+        .map(|(i, area)| {
+            // The centre is always filled, so every shape is connected enough to look real
+            let mut cells = [false; 9];
+            cells[4] = true;
+            let mut order = [0, 1, 2, 3, 5, 6, 7, 8];
+            rng.shuffle(&mut order);
+            *area = rng.range(5..=7) as usize;
+            for &cell in &order[..*area - 1] {
+                cells[cell] = true;
+            }
+
+            let rows = cells
+                .chunks(3)
+                .map(|row| {
+                    row.iter()
+                        .map(|&c| if c { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .join("\n");
+            format!("{i}:\n{rows}")
+        })
+        .join("\n\n");
+
+    let regions = (0..size)
+        .map(|_| {
+            let width = rng.range(35..=50) as usize;
+            let height = rng.range(35..=50) as usize;
+            let fill = if rng.chance(0.5) { 0.6 } else { 1.05 };
+            let mut cells = (width * height) as f64 * fill;
+            let mut counts = [0; 6];
+            while cells > 0.0 {
+                let shape = rng.index(6);
+                counts[shape] += 1;
+                cells -= areas[shape] as f64;
+            }
+
+            format!("{width}x{height}: {}", counts.iter().join(" "))
+        })
+        .join("\n");
+
+    format!("{shapes}\n\n{regions}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn synth_test() {
+        for synth in SYNTHS {
+            let solution = crate::solution(synth.day).unwrap();
+            let input = synth.input(1, synth.typical_size / 4);
+
+            assert_eq!(input, synth.input(1, synth.typical_size / 4));
+            if let Err(e) = solution.parse(&input) {
+                panic!("{e}");
+            }
+        }
+    }
+
+    #[test]
+    pub fn size_test() {
+        for synth in SYNTHS {
+            let len = |size| synth.input(7, size).len();
+            assert!(len(40) > len(10), "day{} ignores its size", synth.day);
+        }
+    }
+}
@@ -1,11 +1,21 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    error::ParseError,
+    inputs::normalize,
+    solution::{Answer, Solution},
+};
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct Object {}
 
+pub fn try_generator(input: &str) -> Result<Object, ParseError> {
+    unimplemented!()
+}
+
 #[aoc_generator(dayN)]
 pub fn generator(input: &str) -> Object {
-    unimplemented!()
+    try_generator(&normalize(input)).unwrap_or_else(|e| panic!("{e}"))
 }
 
 #[aoc(dayN, part1)]
@@ -18,6 +28,25 @@ pub fn part2(inputs: &Object) -> usize {
     unimplemented!()
 }
 
+pub struct Solver;
+
+impl Solution for Solver {
+    const DAY: u8 = N;
+    type Parsed = Object;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        try_generator(input)
+    }
+
+    fn part1(inputs: &Self::Parsed) -> Answer {
+        part1(inputs).into()
+    }
+
+    fn part2(inputs: &Self::Parsed) -> Answer {
+        part2(inputs).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,20 +69,4 @@ mod tests {
     pub fn part2_test() {
         // assert_eq!(part2(&generator(SAMPLE)), 336);
     }
-
-    mod regression {
-        use super::*;
-
-        const INPUT: &str = include_str!("../input/2025/dayN.txt");
-        const ANSWERS: (usize, usize) = (0, 0);
-
-        #[test]
-        pub fn test() {
-            let input = INPUT.trim_end_matches('\n');
-            // let output = generator(input);
-
-            // assert_eq!(part1(&output), ANSWERS.0);
-            // assert_eq!(part2(&output), ANSWERS.1);
-        }
-    }
 }
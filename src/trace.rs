@@ -0,0 +1,130 @@
+//! Execution traces: the steps a solver took, for working out why an answer
+//! is wrong.
+//!
+//! Solvers record steps with [`trace!`](crate::trace!), which compiles to
+//! nothing unless the `trace` feature is on. Even then, steps are only kept
+//! while [`capture`] runs, and only on the thread that called it.
+//!
+//! A step renders as text, `wave removed=13 total=13`, or as a JSON line,
+//! `{"event":"wave","removed":13,"total":13}`.
+
+use std::{cell::RefCell, fmt};
+
+use serde::{Serialize, ser::SerializeMap};
+use serde_json::Value;
+
+/// Records a step with named fields, which can be anything serializable:
+///
+/// ```ignore
+/// trace!("rotate", clicks = x, position = dial);
+/// ```
+///
+/// Without the `trace` feature the fields aren't even evaluated.
+#[macro_export]
+macro_rules! trace {
+    ($event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "trace")]
+        $crate::trace::emit($event, || {
+            vec![$((stringify!($key), $crate::trace::value(&$value))),*]
+        });
+    };
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub event: &'static str,
+    /// In the order they were given.
+    pub fields: Vec<(&'static str, Value)>,
+}
+
+thread_local! {
+    static STEPS: RefCell<Option<Vec<Step>>> = const { RefCell::new(None) };
+}
+
+/// Calls `f`, returning its result and every step it recorded.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<Step>) {
+    let outer = STEPS.replace(Some(Vec::new()));
+    let res = f();
+    let steps = STEPS.replace(outer).unwrap_or_default();
+
+    (res, steps)
+}
+
+/// Records a step if a [`capture`] is running. `fields` is only called then.
+pub fn emit(event: &'static str, fields: impl FnOnce() -> Vec<(&'static str, Value)>) {
+    STEPS.with_borrow_mut(|steps| {
+        if let Some(steps) = steps {
+            steps.push(Step {
+                event,
+                fields: fields(),
+            });
+        }
+    });
+}
+
+pub fn value(value: &impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or(Value::Null)
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.event)?;
+        for (key, value) in &self.fields {
+            write!(f, " {key}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A map with the event first, so a step can be `#[serde(flatten)]`ed.
+impl Serialize for Step {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len() + 1))?;
+        map.serialize_entry("event", self.event)?;
+        for (key, value) in &self.fields {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn capture_test() {
+        emit("ignored", Vec::new);
+        let (answer, steps) = capture(|| {
+            emit("rotate", || {
+                vec![("clicks", value(&-68)), ("zero", value(&false))]
+            });
+            emit("digits", || vec![("picked", value(&[9, 8]))]);
+            42
+        });
+        emit("ignored", Vec::new);
+
+        assert_eq!(answer, 42);
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].to_string(), "rotate clicks=-68 zero=false");
+        assert_eq!(
+            serde_json::to_string(&steps[1]).unwrap(),
+            r#"{"event":"digits","picked":[9,8]}"#
+        );
+    }
+
+    #[cfg(feature = "trace")]
+    #[test]
+    pub fn solver_test() {
+        let (answer, steps) = capture(|| crate::solve(1, crate::solution::Part::One, "L68\nR18"));
+        assert_eq!(answer.unwrap().to_string(), "1");
+        assert_eq!(
+            steps.iter().map(Step::to_string).collect::<Vec<_>>(),
+            [
+                "rotate clicks=-68 position=82 landed=false passes=1",
+                "rotate clicks=18 position=0 landed=true passes=1"
+            ]
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Disjoint sets over `0..n`, with union by size.
+//!
+//! [`UnionFind`] halves paths as it finds, for near-constant time queries.
+//! [`RollbackUnionFind`] never changes a parent except in a union, so unions
+//! can be undone back to a [snapshot](RollbackUnionFind::snapshot).
+//!
+//! Both keep each component's members on a circular list, so a component can
+//! be enumerated in time proportional to its size.
+
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    /// The component size, valid for roots only.
+    size: Vec<usize>,
+    /// The next member of the same component.
+    next: Vec<usize>,
+    components: usize,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        UnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            next: (0..n).collect(),
+            components: n,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    // Path halving was faster than path compression and path splitting for
+    // both parts of day 8.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            self.parent[x] = self.parent[self.parent[x]];
+            x = self.parent[x];
+        }
+
+        x
+    }
+
+    /// Merges the components of `x` and `y`, returning whether they were apart.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut px, mut py) = (self.find(x), self.find(y));
+        if px == py {
+            return false;
+        }
+
+        if self.size[px] < self.size[py] {
+            std::mem::swap(&mut px, &mut py);
+        }
+        self.parent[py] = px;
+        self.size[px] += self.size[py];
+        self.next.swap(px, py);
+        self.components -= 1;
+
+        true
+    }
+
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// The size of the component of `x`.
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// The number of components.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The members of the component of `x`, starting with `x`.
+    pub fn members(&self, x: usize) -> Members<'_> {
+        Members::new(&self.next, x)
+    }
+
+    /// The root of every component, in increasing order.
+    pub fn roots(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len()).filter(|&x| self.parent[x] == x)
+    }
+
+    /// The size of every component, in the order of [`UnionFind::roots`].
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.roots().map(|root| self.size[root])
+    }
+}
+
+/// A snapshot of a [`RollbackUnionFind`], to roll back to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snapshot(usize);
+
+#[derive(Debug, Clone)]
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    next: Vec<usize>,
+    components: usize,
+    /// The root attached to another by each union, most recent last.
+    history: Vec<usize>,
+}
+
+impl RollbackUnionFind {
+    pub fn new(n: usize) -> Self {
+        RollbackUnionFind {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            next: (0..n).collect(),
+            components: n,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Union by size alone keeps trees O(log n) deep.
+    pub fn find(&self, mut x: usize) -> usize {
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+
+        x
+    }
+
+    /// Merges the components of `x` and `y`, returning whether they were apart.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut px, mut py) = (self.find(x), self.find(y));
+        if px == py {
+            return false;
+        }
+
+        if self.size[px] < self.size[py] {
+            std::mem::swap(&mut px, &mut py);
+        }
+        self.parent[py] = px;
+        self.size[px] += self.size[py];
+        self.next.swap(px, py);
+        self.components -= 1;
+        self.history.push(py);
+
+        true
+    }
+
+    pub fn same(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    pub fn size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    pub fn members(&self, x: usize) -> Members<'_> {
+        Members::new(&self.next, x)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.history.len())
+    }
+
+    /// Undoes every union since `snapshot` was taken.
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        while self.history.len() > snapshot.0 {
+            let child = self.history.pop().unwrap();
+            let root = self.parent[child];
+
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+            self.next.swap(root, child);
+            self.components += 1;
+        }
+    }
+}
+
+/// Walks a component's circular list of members.
+#[derive(Debug, Clone)]
+pub struct Members<'a> {
+    next: &'a [usize],
+    start: usize,
+    current: Option<usize>,
+}
+
+impl<'a> Members<'a> {
+    fn new(next: &'a [usize], start: usize) -> Self {
+        Members {
+            next,
+            start,
+            current: Some(start),
+        }
+    }
+}
+
+impl Iterator for Members<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let current = self.current?;
+        let next = self.next[current];
+        self.current = (next != self.start).then_some(next);
+
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    pub fn union_find_test() {
+        let mut uf = UnionFind::new(6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 1));
+        assert!(!uf.union(0, 2));
+        assert!(uf.union(4, 5));
+
+        assert_eq!(uf.components(), 3);
+        assert_eq!(uf.size(2), 3);
+        assert!(uf.same(0, 2));
+        assert!(!uf.same(0, 3));
+        assert_eq!(uf.members(1).sorted().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(uf.members(3).collect::<Vec<_>>(), [3]);
+        assert_eq!(uf.sizes().sorted().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+
+    #[test]
+    pub fn rollback_test() {
+        let mut uf = RollbackUnionFind::new(5);
+        uf.union(0, 1);
+        let snapshot = uf.snapshot();
+        uf.union(1, 2);
+        uf.union(3, 4);
+        uf.union(0, 4);
+        assert_eq!(uf.components(), 1);
+
+        uf.rollback(snapshot);
+        assert_eq!(uf.components(), 4);
+        assert_eq!(uf.size(0), 2);
+        assert!(!uf.same(2, 1));
+        assert_eq!(uf.members(0).sorted().collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(uf.members(4).collect::<Vec<_>>(), [4]);
+    }
+
+    proptest! {
+        #[test]
+        fn naive_test(
+            unions in prop::collection::vec((0..30usize, 0..30usize), 0..60),
+            undo in 0..60usize,
+        ) {
+            let mut uf = UnionFind::new(30);
+            let mut rollback = RollbackUnionFind::new(30);
+            let mut label = (0..30).collect::<Vec<_>>();
+            let mut snapshot = (rollback.snapshot(), label.clone());
+
+            for (i, &(a, b)) in unions.iter().enumerate() {
+                if i == undo {
+                    snapshot = (rollback.snapshot(), label.clone());
+                }
+                let (from, to) = (label[b], label[a]);
+                prop_assert_eq!(uf.union(a, b), from != to);
+                prop_assert_eq!(rollback.union(a, b), from != to);
+                label.iter_mut().filter(|l| **l == from).for_each(|l| *l = to);
+            }
+
+            let check = |label: &[usize], uf: &mut dyn FnMut(usize) -> (usize, Vec<usize>)| {
+                for x in 0..30 {
+                    let (size, members) = uf(x);
+                    let naive = (0..30).filter(|&y| label[y] == label[x]).collect::<Vec<_>>();
+                    prop_assert_eq!(size, naive.len());
+                    prop_assert_eq!(members.into_iter().sorted().collect::<Vec<_>>(), naive);
+                }
+                Ok(())
+            };
+
+            check(&label, &mut |x| (uf.size(x), uf.members(x).collect()))?;
+            prop_assert_eq!(uf.components(), label.iter().unique().count());
+
+            rollback.rollback(snapshot.0);
+            let label = snapshot.1;
+            check(&label, &mut |x| (rollback.size(x), rollback.members(x).collect()))?;
+            prop_assert_eq!(rollback.components(), label.iter().unique().count());
+        }
+    }
+}